@@ -0,0 +1,347 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use crate::{Tall, KARATSUBA_GRENSE};
+
+/// Hvordan et svar med for mange desimaler rundes av til riktig antall
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Avrunding {
+    /// Midt mellom to verdier rundes til den med partall i siste desimal (2.5 blir 2, 3.5 blir 4).
+    /// Avrundingsfeilene går da like ofte opp som ned, så de ikke hoper seg opp i lange summer
+    HalvPar,
+    /// Midt mellom to verdier rundes bort fra null (2.5 blir 3, -2.5 blir -3)
+    HalvOpp,
+    /// Desimalene som ikke får plass kuttes bort, altså avrunding mot null
+    Kutt,
+}
+
+impl Avrunding {
+    /// Tolker navnet på en avrunding slik det skrives på kommandolinjen
+    pub fn fra_navn(navn: &str) -> Option<Self> {
+        match navn {
+            "halv-par" => Some(Self::HalvPar),
+            "halv-opp" => Some(Self::HalvOpp),
+            "kutt" => Some(Self::Kutt),
+            _ => None,
+        }
+    }
+}
+
+/// 10^eksponent
+fn tierpotens(eksponent: u32, grense: usize) -> Tall {
+    Tall::from(10_u64).potens(eksponent as u64, grense)
+}
+
+/// Deler `teller` på `nevner` og runder av til et heltall etter angitt avrunding
+fn del_og_avrund(teller: &Tall, nevner: &Tall, avrunding: Avrunding) -> Result<Tall, String> {
+    // Kvotienten er rundet mot null, og resten har samme fortegn som telleren
+    let (kvotient, rest) = teller.del_med_rest(nevner)?;
+
+    if rest.er_null() {
+        return Ok(kvotient);
+    }
+
+    // Sammenlign resten med en halv nevner, altså 2 * |rest| mot |nevner|
+    let dobbel_rest = rest.abs() + rest.abs();
+    let bort_fra_null = match (avrunding, dobbel_rest.cmp(&nevner.abs())) {
+        (Avrunding::Kutt, _) => false,
+        (_, Ordering::Greater) => true,
+        (_, Ordering::Less) => false,
+        (Avrunding::HalvOpp, Ordering::Equal) => true,
+        (Avrunding::HalvPar, Ordering::Equal) => {
+            let (_, siste) = kvotient.del_med_rest(&Tall::from(2_u64))?;
+            !siste.er_null()
+        }
+    };
+
+    if !bort_fra_null {
+        return Ok(kvotient);
+    }
+
+    // Det eksakte svaret er negativt når teller og nevner har ulikt fortegn, selv om kvotienten ble null
+    if teller.negativ != nevner.negativ {
+        Ok(kvotient - Tall::from(1_u64))
+    } else {
+        Ok(kvotient + Tall::from(1_u64))
+    }
+}
+
+/// Et desimaltall med fast antall desimaler, lagret som et heltall og en skala: verdien er mantisse / 10^skala.
+/// 123.456 lagres som mantisse 123456 og skala 3. Addisjon, subtraksjon og multiplikasjon er eksakte,
+/// og bare divisjon og avrunding må velge hvor mange desimaler svaret skal ha
+#[derive(Clone, Debug)]
+pub struct Desimaltall {
+    mantisse: Tall,
+    skala: u32,
+}
+
+impl Desimaltall {
+    pub fn new(mantisse: Tall, skala: u32) -> Self {
+        Self { mantisse, skala }
+    }
+
+    /// Antall desimaler etter komma
+    pub fn skala(&self) -> u32 {
+        self.skala
+    }
+
+    /// Mantissen skrevet om til en større skala, ved å gange med 10 for hver ekstra desimal
+    fn mantisse_med_skala(&self, skala: u32, grense: usize) -> Tall {
+        self.mantisse
+            .multipliser(&tierpotens(skala - self.skala, grense), grense)
+    }
+
+    /// Mantissene til to tall skrevet om til samme skala, den største av de to
+    fn felles_skala(&self, rhs: &Desimaltall, grense: usize) -> (Tall, Tall, u32) {
+        let skala = u32::max(self.skala, rhs.skala);
+        (
+            self.mantisse_med_skala(skala, grense),
+            rhs.mantisse_med_skala(skala, grense),
+            skala,
+        )
+    }
+
+    /// Multipliserer to desimaltall, og bruker Karatsuba når mantissene har minst `grense` blokker.
+    /// Produktet har like mange desimaler som faktorene til sammen, så ingenting går tapt
+    pub fn multipliser(&self, rhs: &Desimaltall, grense: usize) -> Desimaltall {
+        Desimaltall::new(
+            self.mantisse.multipliser(&rhs.mantisse, grense),
+            self.skala + rhs.skala,
+        )
+    }
+
+    /// Runder av til angitt antall desimaler. Har tallet allerede færre desimaler fylles det ut med nuller
+    pub fn avrund(&self, desimaler: u32, avrunding: Avrunding, grense: usize) -> Desimaltall {
+        if desimaler >= self.skala {
+            return Desimaltall::new(self.mantisse_med_skala(desimaler, grense), desimaler);
+        }
+
+        let nevner = tierpotens(self.skala - desimaler, grense);
+        let mantisse =
+            del_og_avrund(&self.mantisse, &nevner, avrunding).expect("10^n er ikke null");
+
+        Desimaltall::new(mantisse, desimaler)
+    }
+
+    /// Divisjon med angitt antall desimaler i svaret. Med a = m_a / 10^s_a og b = m_b / 10^s_b er
+    /// a / b * 10^desimaler = m_a * 10^(s_b + desimaler) / (m_b * 10^s_a), som rundes av til et heltall
+    pub fn del(
+        &self,
+        rhs: &Desimaltall,
+        desimaler: u32,
+        avrunding: Avrunding,
+        grense: usize,
+    ) -> Result<Desimaltall, String> {
+        let teller = self
+            .mantisse
+            .multipliser(&tierpotens(rhs.skala + desimaler, grense), grense);
+        let nevner = rhs
+            .mantisse
+            .multipliser(&tierpotens(self.skala, grense), grense);
+
+        Ok(Desimaltall::new(
+            del_og_avrund(&teller, &nevner, avrunding)?,
+            desimaler,
+        ))
+    }
+}
+
+// Tolker et desimaltall med valgfritt minustegn og punktum som desimalskille, som 123.456, -0.5 eller 42
+impl FromStr for Desimaltall {
+    type Err = String;
+
+    fn from_str(streng: &str) -> Result<Self, Self::Err> {
+        let (negativ, tall) = match streng.strip_prefix('-') {
+            Some(tall) => (true, tall),
+            None => (false, streng),
+        };
+
+        let (heltall, desimaler) = tall.split_once('.').unwrap_or((tall, ""));
+
+        if heltall.is_empty() && desimaler.is_empty() {
+            return Err(format!("Ugyldig desimaltall \"{streng}\""));
+        }
+
+        let mantisse = Tall::fra_grunntall(&format!("{heltall}{desimaler}"), 10)
+            .map_err(|melding| format!("Ugyldig desimaltall \"{streng}\": {melding}"))?;
+
+        Ok(Desimaltall::new(
+            mantisse.med_fortegn(negativ),
+            desimaler.len() as u32,
+        ))
+    }
+}
+
+// Skriver tallet med alle desimalene i skalaen, også nuller på slutten, så 1.50 forblir 1.50
+impl fmt::Display for Desimaltall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut sifre = self.mantisse.til_grunntall(10);
+        let skala = self.skala as usize;
+
+        if skala > 0 {
+            // Fyll ut med nuller foran så det alltid er minst ett siffer før komma
+            if sifre.len() <= skala {
+                sifre.insert_str(0, &"0".repeat(skala + 1 - sifre.len()));
+            }
+            sifre.insert(sifre.len() - skala, '.');
+        }
+
+        f.pad_integral(!self.mantisse.negativ, "", &sifre)
+    }
+}
+
+impl std::ops::Add<Desimaltall> for Desimaltall {
+    type Output = Desimaltall;
+
+    fn add(self, rhs: Desimaltall) -> Self::Output {
+        let (a, b, skala) = self.felles_skala(&rhs, KARATSUBA_GRENSE);
+        Desimaltall::new(a + b, skala)
+    }
+}
+
+impl std::ops::Sub<Desimaltall> for Desimaltall {
+    type Output = Desimaltall;
+
+    fn sub(self, rhs: Desimaltall) -> Self::Output {
+        let (a, b, skala) = self.felles_skala(&rhs, KARATSUBA_GRENSE);
+        Desimaltall::new(a - b, skala)
+    }
+}
+
+impl std::ops::Mul<Desimaltall> for Desimaltall {
+    type Output = Desimaltall;
+
+    fn mul(self, rhs: Desimaltall) -> Self::Output {
+        self.multipliser(&rhs, KARATSUBA_GRENSE)
+    }
+}
+
+impl std::ops::Neg for Desimaltall {
+    type Output = Desimaltall;
+
+    fn neg(self) -> Self::Output {
+        Desimaltall::new(-self.mantisse, self.skala)
+    }
+}
+
+// Desimaltall sammenlignes etter verdi, så 1.5 og 1.50 er like selv om skalaen er ulik
+impl Ord for Desimaltall {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b, _) = self.felles_skala(other, KARATSUBA_GRENSE);
+        a.cmp(&b)
+    }
+}
+
+impl PartialOrd for Desimaltall {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Desimaltall {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Desimaltall {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn desimaltall(streng: &str) -> Desimaltall {
+        streng.parse().unwrap()
+    }
+
+    /// Deler a på b med angitt antall desimaler, og gir svaret som tekst
+    fn del(a: &str, b: &str, desimaler: u32, avrunding: Avrunding) -> String {
+        desimaltall(a)
+            .del(&desimaltall(b), desimaler, avrunding, KARATSUBA_GRENSE)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn tolking_og_utskrift() {
+        assert_eq!(desimaltall("-.5").to_string(), "-0.5");
+        assert_eq!(desimaltall("1.50").to_string(), "1.50");
+        assert_eq!(desimaltall("0.007").to_string(), "0.007");
+        assert_eq!(desimaltall("42").to_string(), "42");
+        assert!("".parse::<Desimaltall>().is_err());
+        assert!(".".parse::<Desimaltall>().is_err());
+        assert!("1.2.3".parse::<Desimaltall>().is_err());
+    }
+
+    #[test]
+    fn addisjon_og_subtraksjon_er_eksakte() {
+        assert_eq!((desimaltall("0.1") + desimaltall("0.2")).to_string(), "0.3");
+        assert_eq!(
+            (desimaltall("10") - desimaltall("0.01")).to_string(),
+            "9.99"
+        );
+        assert_eq!(
+            (desimaltall("0.5") - desimaltall("1.25")).to_string(),
+            "-0.75"
+        );
+    }
+
+    #[test]
+    fn multiplikasjon_beholder_alle_desimaler() {
+        assert_eq!(
+            (desimaltall("1.50") * desimaltall("-0.3")).to_string(),
+            "-0.450"
+        );
+
+        // Med og uten Karatsuba skal gi samme svar
+        let a = desimaltall("123456789012345678901234567890.123456789");
+        let b = desimaltall("-98765432109876543210.98765432109876543210");
+        assert_eq!(a.multipliser(&b, 2), a.multipliser(&b, 100));
+    }
+
+    #[test]
+    fn sammenligning_ser_bort_fra_skala() {
+        assert!(desimaltall("1.5") == desimaltall("1.500"));
+        assert!(desimaltall("-0.1") < desimaltall("0.01"));
+        assert!(desimaltall("2") > desimaltall("1.999"));
+    }
+
+    #[test]
+    fn avrunding_ved_deling() {
+        assert_eq!(del("5", "2", 0, Avrunding::HalvPar), "2");
+        assert_eq!(del("7", "2", 0, Avrunding::HalvPar), "4");
+        assert_eq!(del("5", "2", 0, Avrunding::HalvOpp), "3");
+        assert_eq!(del("-5", "2", 0, Avrunding::HalvOpp), "-3");
+        assert_eq!(del("-0.4", "1", 0, Avrunding::HalvOpp), "0");
+        assert_eq!(del("-0.6", "1", 0, Avrunding::HalvPar), "-1");
+        assert_eq!(del("9.99", "1", 0, Avrunding::Kutt), "9");
+        assert_eq!(del("1", "3", 4, Avrunding::HalvPar), "0.3333");
+        assert_eq!(del("2", "3", 4, Avrunding::HalvPar), "0.6667");
+    }
+
+    #[test]
+    fn deling_på_null_gir_feil() {
+        assert!(desimaltall("1")
+            .del(&desimaltall("0.0"), 2, Avrunding::Kutt, KARATSUBA_GRENSE)
+            .is_err());
+    }
+
+    #[test]
+    fn avrund() {
+        let tall = desimaltall("2.345");
+        assert_eq!(
+            tall.avrund(2, Avrunding::HalvPar, KARATSUBA_GRENSE)
+                .to_string(),
+            "2.34"
+        );
+        assert_eq!(
+            tall.avrund(2, Avrunding::HalvOpp, KARATSUBA_GRENSE)
+                .to_string(),
+            "2.35"
+        );
+        assert_eq!(
+            tall.avrund(5, Avrunding::Kutt, KARATSUBA_GRENSE)
+                .to_string(),
+            "2.34500"
+        );
+    }
+}
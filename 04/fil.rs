@@ -0,0 +1,152 @@
+use std::{
+    fs::File,
+    io::{self, Read, Write},
+};
+
+/// Måten en tekst deles opp i ord på før ordene settes inn i treet
+#[derive(Clone, Copy)]
+pub enum Tokenisering {
+    /// Del på mellomrom og linjeskift, tegnsetting blir med i ordene
+    Mellomrom,
+    /// Del på alt som ikke er bokstaver eller tall, så tegnsetting fjernes
+    Ord,
+    /// Hver linje blir ett ord
+    Linje,
+}
+
+impl Tokenisering {
+    /// Tolker navnet på en tokenisering slik det skrives på kommandolinjen
+    pub fn fra_navn(navn: &str) -> Option<Self> {
+        match navn {
+            "mellomrom" => Some(Self::Mellomrom),
+            "ord" => Some(Self::Ord),
+            "linje" => Some(Self::Linje),
+            _ => None,
+        }
+    }
+
+    /// Deler opp en tekst i ord, uten tomme ord
+    pub fn del_opp(&self, tekst: &str) -> Vec<String> {
+        let ord: Vec<&str> = match self {
+            Self::Mellomrom => tekst.split_whitespace().collect(),
+            Self::Ord => tekst.split(|tegn: char| !tegn.is_alphanumeric()).collect(),
+            Self::Linje => tekst.lines().map(str::trim).collect(),
+        };
+
+        ord.into_iter()
+            .filter(|ord| !ord.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Leser hele innholdet i en tekstfil, eller fra standard input om filstien er "-"
+pub fn les_tekst(filsti: &str) -> Result<String, String> {
+    let mut tekst = String::new();
+
+    if filsti == "-" {
+        return match io::stdin().read_to_string(&mut tekst) {
+            Ok(_) => Ok(tekst),
+            Err(e) => Err(format!(
+                "Kunne ikke lese fra standard input\nFeilmelding: \"{e}\""
+            )),
+        };
+    }
+
+    let mut f = match File::open(filsti) {
+        Ok(handle) => handle,
+        Err(e) => {
+            return Err(format!(
+                "Kunne ikke åpne filen {filsti}\nFeilmelding: \"{e}\""
+            ));
+        }
+    };
+
+    match f.read_to_string(&mut tekst) {
+        Ok(_) => Ok(tekst),
+        Err(e) => Err(format!(
+            "Kunne ikke lese fra filen {filsti}\nFeilmelding: \"{e}\""
+        )),
+    }
+}
+
+/// Skriver en tekst til fil, og overskriver filen om den finnes fra før
+pub fn skriv_tekst(filsti: &str, tekst: &str) -> Result<(), String> {
+    let mut f = match File::create(filsti) {
+        Ok(handle) => handle,
+        Err(e) => {
+            return Err(format!(
+                "Kunne ikke åpne {filsti} for å skrive\nFeilmelding: \"{e}\""
+            ));
+        }
+    };
+
+    match f.write_all(tekst.as_bytes()) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!(
+            "Kunne ikke skrive til {filsti}\nFeilmelding: \"{e}\""
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::*;
+
+    #[test]
+    fn tokenisering() {
+        let tekst = "Hei, du!\n  Hva heter\tdu?\n\n";
+
+        assert_eq!(
+            Tokenisering::Mellomrom.del_opp(tekst),
+            ["Hei,", "du!", "Hva", "heter", "du?"]
+        );
+        assert_eq!(
+            Tokenisering::Ord.del_opp(tekst),
+            ["Hei", "du", "Hva", "heter", "du"]
+        );
+        assert_eq!(
+            Tokenisering::Linje.del_opp(tekst),
+            ["Hei, du!", "Hva heter\tdu?"]
+        );
+        assert!(Tokenisering::Ord.del_opp(" ,.! ").is_empty());
+    }
+
+    #[test]
+    fn tokenisering_fra_navn() {
+        assert!(matches!(
+            Tokenisering::fra_navn("ord"),
+            Some(Tokenisering::Ord)
+        ));
+        assert!(matches!(
+            Tokenisering::fra_navn("linje"),
+            Some(Tokenisering::Linje)
+        ));
+        assert!(matches!(
+            Tokenisering::fra_navn("mellomrom"),
+            Some(Tokenisering::Mellomrom)
+        ));
+        assert!(Tokenisering::fra_navn("Ord").is_none());
+    }
+
+    #[test]
+    fn skriv_og_les() {
+        let filsti = env::temp_dir().join(format!("søketre-fil-{}.txt", process::id()));
+        let filsti = filsti.to_str().unwrap();
+
+        let tekst = "første linje\r\nandre \\ linje\n";
+        skriv_tekst(filsti, tekst).unwrap();
+        assert_eq!(les_tekst(filsti).unwrap(), tekst);
+
+        // Filen overskrives
+        skriv_tekst(filsti, "ny").unwrap();
+        assert_eq!(les_tekst(filsti).unwrap(), "ny");
+
+        fs::remove_file(filsti).unwrap();
+        assert!(les_tekst(filsti)
+            .unwrap_err()
+            .starts_with("Kunne ikke åpne filen"));
+    }
+}
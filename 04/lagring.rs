@@ -0,0 +1,276 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::Node;
+
+/// Markør for en tom node i den lagrede utgaven av treet
+const TOM: &str = "-";
+
+/// Lagring og gjenoppretting av et tre med nøyaktig samme form.
+///
+/// Treet skrives ut i preorden med én node per linje, på formen "antall verdi",
+/// og tomme under-noder skrives som "-". Siden også de tomme nodene er med
+/// er det nok informasjon til å bygge opp igjen nøyaktig samme tre
+impl<T: Ord + Display + Clone> Node<T> {
+    /// Gjør om treet til tekst som kan skrives til fil
+    pub fn serialiser(&self) -> String {
+        let mut buffer = String::new();
+        self.skriv_preorden(&mut buffer);
+        buffer
+    }
+
+    fn skriv_preorden(&self, buffer: &mut String) {
+        match self {
+            Self::Tom => {
+                buffer.push_str(TOM);
+                buffer.push('\n');
+            }
+            Self::Node {
+                verdi,
+                antall,
+                venstre,
+                høyre,
+            } => {
+                // Linjeskift i verdien ville ødelagt formatet, så de (og \) escapes.
+                // \r escapes også, ellers ville `lines` fjernet den fra slutten av verdien
+                let verdi = verdi
+                    .to_string()
+                    .replace('\\', "\\\\")
+                    .replace('\n', "\\n")
+                    .replace('\r', "\\r");
+                buffer.push_str(&format!("{antall} {verdi}\n"));
+                venstre.skriv_preorden(buffer);
+                høyre.skriv_preorden(buffer);
+            }
+        }
+    }
+}
+
+impl<T: Ord + Display + Clone + FromStr> Node<T> {
+    /// Bygger opp igjen et tre fra tekst laget av `serialiser`.
+    /// Gir feil om teksten ikke beskriver et gyldig søketre
+    pub fn deserialiser(tekst: &str) -> Result<Self, String> {
+        let mut linjer = tekst.lines().enumerate();
+        let tre = Self::les_preorden(&mut linjer)?;
+
+        if let Some((linjetall, _)) = linjer.next() {
+            return Err(format!(
+                "Uventet innhold etter slutten av treet på linje {}",
+                linjetall + 1
+            ));
+        }
+
+        // Et tre med verdier i feil rekkefølge ville gitt feil svar ved søk, så det avvises
+        let verdier = tre.som_telt_liste();
+        if verdier.windows(2).any(|par| par[0].0 >= par[1].0) {
+            return Err("Verdiene i filen danner ikke et gyldig søketre".to_string());
+        }
+
+        Ok(tre)
+    }
+
+    fn les_preorden<'a>(
+        linjer: &mut impl Iterator<Item = (usize, &'a str)>,
+    ) -> Result<Self, String> {
+        let (linjetall, linje) = match linjer.next() {
+            Some(linje) => linje,
+            None => return Err("Filen slutter før treet er komplett".to_string()),
+        };
+
+        if linje == TOM {
+            return Ok(Self::Tom);
+        }
+
+        let (antall, verdi) = match linje.split_once(' ') {
+            Some(deler) => deler,
+            None => return Err(format!("Ugyldig node på linje {}", linjetall + 1)),
+        };
+
+        let antall: usize = match antall.parse() {
+            Ok(antall) if antall > 0 => antall,
+            _ => {
+                return Err(format!(
+                    "Ugyldig antall \"{antall}\" på linje {}",
+                    linjetall + 1
+                ))
+            }
+        };
+
+        let verdi: T = match Self::fjern_escaping(verdi).parse() {
+            Ok(verdi) => verdi,
+            Err(_) => {
+                return Err(format!(
+                    "Ugyldig verdi \"{verdi}\" på linje {}",
+                    linjetall + 1
+                ))
+            }
+        };
+
+        Ok(Self::Node {
+            verdi,
+            antall,
+            venstre: Box::new(Self::les_preorden(linjer)?),
+            høyre: Box::new(Self::les_preorden(linjer)?),
+        })
+    }
+
+    fn fjern_escaping(verdi: &str) -> String {
+        let mut resultat = String::with_capacity(verdi.len());
+        let mut tegn = verdi.chars();
+
+        while let Some(bokstav) = tegn.next() {
+            match (bokstav, tegn.clone().next()) {
+                ('\\', Some('n')) => {
+                    resultat.push('\n');
+                    tegn.next();
+                }
+                ('\\', Some('r')) => {
+                    resultat.push('\r');
+                    tegn.next();
+                }
+                ('\\', Some('\\')) => {
+                    resultat.push('\\');
+                    tegn.next();
+                }
+                (bokstav, _) => resultat.push(bokstav),
+            }
+        }
+
+        resultat
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tre(verdier: &[&str]) -> Node<String> {
+        let mut tre = Node::ny_tom();
+        for verdi in verdier {
+            tre.sett_inn_telt(verdi.to_string());
+        }
+        tre
+    }
+
+    /// Gjenoppretter treet og sjekker at det skrives ut likt igjen, så formen er den samme
+    fn tur_retur(tre: &Node<String>) -> Node<String> {
+        let tekst = tre.serialiser();
+        let gjenopprettet = Node::<String>::deserialiser(&tekst).unwrap();
+        assert_eq!(gjenopprettet.serialiser(), tekst);
+        gjenopprettet
+    }
+
+    fn feil(tekst: &str) -> String {
+        match Node::<String>::deserialiser(tekst) {
+            Ok(_) => panic!("{tekst:?} skulle gitt feil"),
+            Err(melding) => melding,
+        }
+    }
+
+    #[test]
+    fn tomt_tre() {
+        let tom = tre(&[]);
+        assert_eq!(tom.serialiser(), "-\n");
+        assert!(tur_retur(&tom).er_tom());
+    }
+
+    #[test]
+    fn beholder_form_og_antall() {
+        let original = tre(&["m", "c", "x", "a", "e", "c", "x", "x"]);
+        assert_eq!(
+            original.serialiser(),
+            "1 m\n2 c\n1 a\n-\n-\n1 e\n-\n-\n3 x\n-\n-\n"
+        );
+
+        let gjenopprettet = tur_retur(&original);
+        assert_eq!(gjenopprettet.som_telt_liste(), original.som_telt_liste());
+        assert_eq!(gjenopprettet.høyde(), original.høyde());
+    }
+
+    #[test]
+    fn spesialtegn_escapes() {
+        let verdier = ["a\\b", "linje\nskift", "\\n", "slutt\\", "mellom rom", ""];
+        let original = tre(&verdier);
+
+        let tekst = original.serialiser();
+        assert!(tekst.contains("1 a\\\\b\n"));
+        assert!(tekst.contains("1 linje\\nskift\n"));
+        assert!(tekst.contains("1 \\\\n\n"));
+        assert_eq!(tekst.lines().count(), 2 * verdier.len() + 1);
+
+        assert_eq!(tur_retur(&original).som_liste(), original.som_liste());
+    }
+
+    #[test]
+    fn vognretur_på_slutten_av_verdien_beholdes() {
+        let original = tre(&["b\r", "a", "c\r\n", "\r"]);
+
+        let tekst = original.serialiser();
+        assert!(!tekst.contains('\r'));
+        assert_eq!(
+            tur_retur(&original).som_liste(),
+            ["\r", "a", "b\r", "c\r\n"]
+        );
+    }
+
+    #[test]
+    fn godtar_windows_linjeskift() {
+        let tekst = tre(&["b", "a"]).serialiser().replace('\n', "\r\n");
+        assert_eq!(
+            Node::<String>::deserialiser(&tekst).unwrap().som_liste(),
+            ["a", "b"]
+        );
+    }
+
+    #[test]
+    fn avviser_usorterte_verdier() {
+        assert_eq!(
+            feil("1 a\n1 b\n-\n-\n-\n"),
+            "Verdiene i filen danner ikke et gyldig søketre"
+        );
+        // Samme verdi to ganger skal heller ikke godtas
+        assert_eq!(
+            feil("1 a\n1 a\n-\n-\n-\n"),
+            "Verdiene i filen danner ikke et gyldig søketre"
+        );
+    }
+
+    #[test]
+    fn avviser_ugyldig_antall() {
+        assert_eq!(feil("0 a\n-\n-\n"), "Ugyldig antall \"0\" på linje 1");
+        assert_eq!(
+            feil("1 b\nx a\n-\n-\n-\n"),
+            "Ugyldig antall \"x\" på linje 2"
+        );
+        assert_eq!(feil("-1 a\n-\n-\n"), "Ugyldig antall \"-1\" på linje 1");
+        assert_eq!(feil("a\n-\n-\n"), "Ugyldig node på linje 1");
+    }
+
+    #[test]
+    fn avviser_ugyldig_verdi() {
+        match Node::<u32>::deserialiser("1 tolv\n-\n-\n") {
+            Ok(_) => panic!("\"tolv\" er ikke et tall"),
+            Err(melding) => assert_eq!(melding, "Ugyldig verdi \"tolv\" på linje 1"),
+        }
+    }
+
+    #[test]
+    fn avviser_avkortet_og_overflødig_innhold() {
+        let tekst = tre(&["m", "c", "x"]).serialiser();
+        let linjer: Vec<&str> = tekst.lines().collect();
+
+        for lengde in 0..linjer.len() {
+            assert_eq!(
+                feil(&linjer[..lengde].join("\n")),
+                "Filen slutter før treet er komplett"
+            );
+        }
+
+        assert_eq!(
+            feil(&format!("{tekst}-\n")),
+            format!(
+                "Uventet innhold etter slutten av treet på linje {}",
+                linjer.len() + 1
+            )
+        );
+    }
+}
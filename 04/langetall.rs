@@ -1,150 +1,371 @@
-use std::{env, fmt, ops::Add, ops::Sub, process::exit, ptr::NonNull};
-
-// Fortell kompilatoren at denne typen er trivielt kopierbar
-#[derive(Clone, Copy)]
+use std::{
+    cmp::Ordering,
+    env, fmt,
+    process::exit,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+mod desimaltall;
+mod oppstilling;
+mod tallsystem;
+mod tallteori;
+mod uttrykk;
+
+use desimaltall::{Avrunding, Desimaltall};
+use tallsystem::Utskrift;
+
+/// Grunntallet tallene lagres i. Hver blokk holder 9 desimale siffer, så en blokk får plass i en u32
+/// og produktet av to blokker får plass i en u64
+const BASE: u32 = 1_000_000_000;
+const SIFFER_PER_BLOKK: usize = 9;
+
+/// Antall blokker der multiplikasjon går over fra skolebok-metoden til Karatsuba.
+/// Under grensen er skolebok-metoden raskere, siden Karatsuba har mer arbeid per steg
+const KARATSUBA_GRENSE: usize = 32;
+
+/// Antall desimaler i svaret når desimaltall deles på hverandre, om ikke noe annet er angitt
+const DESIMALER: u32 = 10;
+
+// Et Tall lagres som blokker på 9 siffer i én sammenhengende Vec, med minst signifikante blokk først.
+// Det er alltid minst én blokk, og ingen nullblokker i starten av tallet, så hvert tall har bare én form.
+// 12345678901 lagres for eksempel som [345678901, 12]
+#[derive(Clone, Debug)]
 struct Tall {
-    første: Option<NonNull<Siffer>>,
-    siste: Option<NonNull<Siffer>>,
-    antall_siffer: usize,
-}
-
-struct Siffer {
-    verdi: u32,
-    neste: Option<NonNull<Siffer>>,
-    forrige: Option<NonNull<Siffer>>,
+    blokker: Vec<u32>,
+    /// Fortegnet til tallet, blokkene er alltid tallverdien. Null er aldri negativ
+    negativ: bool,
 }
 
-impl Siffer {
-    /// Konstruktør, tar inn en verdi og genererer tilsvarende Siffer-objekt
-    /// Kan returnere None om verdien som gis ikke er et gyldig siffer
-    /// (mindre enn 0 eller større enn 9)
-    pub fn new(verdi: u32) -> Option<Self> {
-        // Pass på at et siffer bare har ett siffer
-        if verdi > 9 {
-            None
-        } else {
-            Some(Self {
-                verdi,
-                neste: None,
-                forrige: None,
-            })
+impl Tall {
+    /// Opprett et Tall fra en String, med valgfritt minustegn og prefiks (0b, 0o eller 0x).
+    /// Avslutter programmet med en feilmelding om strengen ikke er et gyldig tall
+    pub fn fra_streng(streng: String) -> Self {
+        match streng.parse() {
+            Ok(tall) => tall,
+            Err(melding) => {
+                println!("{melding}");
+                exit(1);
+            }
         }
     }
-}
 
-impl Tall {
-    /// Konstruktør
-    pub const fn new() -> Self {
+    /// Bygger et positivt Tall fra blokker med minst signifikante blokk først.
+    /// Nullblokker i starten av tallet fjernes, men tallet får alltid minst én blokk
+    fn fra_blokker(mut blokker: Vec<u32>) -> Self {
+        blokker.truncate(lengde_uten_nuller(&blokker));
+
+        if blokker.is_empty() {
+            blokker.push(0);
+        }
+
         Self {
-            første: None,
-            siste: None,
-            antall_siffer: 0,
+            blokker,
+            negativ: false,
         }
     }
 
-    /// Hjelpemetode, sjekk om et Tall inneholder noen Siffer eller ikke
-    pub fn er_tomt(&self) -> bool {
-        self.første.is_none()
+    /// Sjekk om et Tall er null
+    pub fn er_null(&self) -> bool {
+        self.blokker == [0]
     }
 
-    /// Sett inn et nytt Siffer på begynnelsen av et Tall (venstre)
-    pub fn sett_inn_foran(&mut self, verdi: u32) {
-        let mut siffer = Box::new(match Siffer::new(verdi) {
-            Some(siffer) => siffer,
-            None => return,
-        });
+    /// Gir tallet angitt fortegn, men null forblir positiv
+    fn med_fortegn(mut self, negativ: bool) -> Self {
+        self.negativ = negativ && !self.er_null();
+        self
+    }
 
-        unsafe {
-            siffer.neste = self.første;
-            siffer.forrige = None;
-            let siffer: Option<NonNull<Siffer>> = Some(Box::leak(siffer).into());
+    /// Multipliserer to Tall, og bruker Karatsuba-algoritmen når begge tallene har minst `grense` blokker
+    pub fn multipliser(&self, rhs: &Tall, grense: usize) -> Tall {
+        // Karatsuba deler tallene i to helt ned til grensen, så den må være minst 2
+        let grense = usize::max(grense, 2);
 
-            match self.første {
-                None => self.siste = siffer,
-                Some(første) => (*første.as_ptr()).forrige = siffer,
-            }
+        Tall::fra_blokker(karatsuba(&self.blokker, &rhs.blokker, grense))
+            .med_fortegn(self.negativ != rhs.negativ)
+    }
 
-            self.første = siffer;
-            self.antall_siffer += 1;
+    /// Lang divisjon, gir kvotient og rest. Gir feil i stedet for å krasje ved deling på null.
+    /// Som for heltall i Rust rundes kvotienten mot null, og resten får samme fortegn som dividenden
+    pub fn del_med_rest(&self, divisor: &Tall) -> Result<(Tall, Tall), String> {
+        if divisor.er_null() {
+            return Err("Kan ikke dele på null".to_string());
         }
+
+        // Med en divisor på én blokk går hver blokk i kvotienten rett fra en u64-divisjon
+        if let [divisor_blokk] = divisor.blokker[..] {
+            let mut kvotient = self.blokker.clone();
+            let rest = del_på(&mut kvotient, divisor_blokk);
+
+            return Ok((
+                Tall::fra_blokker(kvotient).med_fortegn(self.negativ != divisor.negativ),
+                Tall::fra_blokker(vec![rest]).med_fortegn(self.negativ),
+            ));
+        }
+
+        let mut kvotient = vec![0; self.blokker.len()];
+        let mut rest: Vec<u32> = Vec::with_capacity(divisor.blokker.len() + 1);
+
+        // Gå gjennom dividenden fra mest signifikante blokk, som ved divisjon på papir
+        for (i, &blokk) in self.blokker.iter().enumerate().rev() {
+            // Flytt ned neste blokk: rest = rest * 10^9 + blokk
+            rest.insert(0, blokk);
+            rest.truncate(lengde_uten_nuller(&rest));
+
+            kvotient[i] = kvotientblokk(&mut rest, &divisor.blokker);
+        }
+
+        Ok((
+            Tall::fra_blokker(kvotient).med_fortegn(self.negativ != divisor.negativ),
+            Tall::fra_blokker(rest).med_fortegn(self.negativ),
+        ))
     }
 
-    /// Sett inn et nytt Siffer på slutten av et Tall (høyre)
-    pub fn sett_inn_bak(&mut self, verdi: u32) {
-        let mut siffer = Box::new(match Siffer::new(verdi) {
-            Some(siffer) => siffer,
-            None => return,
-        });
+    /// Summerer tallverdiene til to Tall, uten å se på fortegnet
+    fn sum_absolutt(self, rhs: Tall) -> Tall {
+        // Det lengste tallet gjenbrukes som resultat, med plass til mente helt til venstre
+        let (lengste, korteste) = if self.blokker.len() >= rhs.blokker.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
 
-        unsafe {
-            siffer.forrige = self.siste;
-            siffer.neste = None;
-            let siffer: Option<NonNull<Siffer>> = Some(Box::leak(siffer).into());
+        let mut sum = lengste.blokker;
+        sum.push(0);
+        legg_til(&mut sum, &korteste.blokker, 0);
 
-            match self.siste {
-                Some(siste) => (*siste.as_ptr()).neste = siffer,
-                None => self.første = siffer,
-            }
+        Tall::fra_blokker(sum)
+    }
+
+    /// Trekker tallverdien til `rhs` fra tallverdien til `self`, uten å se på fortegnet.
+    /// Tallverdien til `self` må være minst like stor som tallverdien til `rhs`
+    fn differanse_absolutt(self, rhs: Tall) -> Tall {
+        let mut differanse = self.blokker;
+        trekk_fra(&mut differanse, &rhs.blokker);
 
-            self.siste = siffer;
-            self.antall_siffer += 1;
+        Tall::fra_blokker(differanse)
+    }
+
+    /// Sammenligner tallverdiene til to Tall, uten å se på fortegnet
+    fn sammenlign_absolutt(&self, rhs: &Tall) -> Ordering {
+        sammenlign(&self.blokker, &rhs.blokker)
+    }
+}
+
+/// Antall blokker med minst signifikante blokk først, uten nullblokker i starten av tallet
+fn lengde_uten_nuller(blokker: &[u32]) -> usize {
+    blokker
+        .iter()
+        .rposition(|&blokk| blokk != 0)
+        .map_or(0, |i| i + 1)
+}
+
+/// Sammenligner to blokk-vektorer med minst signifikante blokk først
+fn sammenlign(a: &[u32], b: &[u32]) -> Ordering {
+    let a = &a[..lengde_uten_nuller(a)];
+    let b = &b[..lengde_uten_nuller(b)];
+
+    // Et tall med flere blokker er størst, ellers avgjør første blokk som er ulik
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+/// Skolebok-multiplikasjon: hver blokk i det ene tallet ganges med hver blokk i det andre.
+/// Mente føres videre for hver rad, siden summen av mange produkter på 10^18 ikke får plass i en u64
+fn skolebok(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut produkt = vec![0; a.len() + b.len()];
+
+    for (i, &blokk_a) in a.iter().enumerate() {
+        if blokk_a == 0 {
+            continue;
         }
+
+        let mut mente = 0;
+
+        for (j, &blokk_b) in b.iter().enumerate() {
+            // Maks (10^9 - 1) + (10^9 - 1)^2 + (10^9 - 1), som får plass i en u64
+            let verdi = produkt[i + j] as u64 + blokk_a as u64 * blokk_b as u64 + mente;
+            produkt[i + j] = (verdi % BASE as u64) as u32;
+            mente = verdi / BASE as u64;
+        }
+
+        produkt[i + b.len()] = mente as u32;
     }
 
-    /// Opprett et Tall fra en String ved å iterere over bokstavene i String og konvertere dem til Siffer
-    pub fn fra_streng(streng: String) -> Self {
-        let mut tall = Tall::new();
-
-        for bokstav in streng.chars() {
-            tall.sett_inn_bak(match bokstav.to_digit(10) {
-                Some(verdi) => verdi,
-                None => {
-                    println!("Ugyldig tegn \"{bokstav}\" i tallet \"{streng}\"");
-                    exit(1);
-                }
-            })
+    produkt
+}
+
+/// Karatsuba-multiplikasjon. Med a = a1 * B^m + a0 og b = b1 * B^m + b0, der B = 10^9, er
+/// a * b = z2 * B^2m + z1 * B^m + z0, der z2 = a1 * b1, z0 = a0 * b0 og
+/// z1 = (a0 + a1)(b0 + b1) - z2 - z0, så det trengs tre multiplikasjoner av halv lengde i stedet for fire
+fn karatsuba(a: &[u32], b: &[u32], grense: usize) -> Vec<u32> {
+    let a = &a[..lengde_uten_nuller(a)];
+    let b = &b[..lengde_uten_nuller(b)];
+
+    if a.len() < grense || b.len() < grense {
+        return skolebok(a, b);
+    }
+
+    let m = usize::max(a.len(), b.len()) / 2;
+    let (a0, a1) = a.split_at(usize::min(m, a.len()));
+    let (b0, b1) = b.split_at(usize::min(m, b.len()));
+
+    let z0 = karatsuba(a0, b0, grense);
+    let z2 = karatsuba(a1, b1, grense);
+    let mut z1 = karatsuba(&blokksum(a0, a1), &blokksum(b0, b1), grense);
+    trekk_fra(&mut z1, &z0);
+    trekk_fra(&mut z1, &z2);
+
+    let mut produkt = vec![0; a.len() + b.len() + 1];
+    legg_til(&mut produkt, &z0, 0);
+    legg_til(&mut produkt, &z1, m);
+    legg_til(&mut produkt, &z2, 2 * m);
+
+    produkt
+}
+
+/// Summen av to blokk-vektorer, minst signifikante blokk først
+fn blokksum(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut sum = a.to_vec();
+    sum.resize(usize::max(a.len(), b.len()) + 1, 0);
+    legg_til(&mut sum, b, 0);
+    sum
+}
+
+/// Legger `b * 10^(9 * forskyvning)` til `a`. `a` må ha plass til resultatet
+fn legg_til(a: &mut [u32], b: &[u32], forskyvning: usize) {
+    let mut mente = 0;
+    let mut i = forskyvning;
+
+    // To blokker og mente summerer til under 2 * 10^9 + 1, som får plass i en u32
+    for &blokk in &b[..lengde_uten_nuller(b)] {
+        let sum = a[i] + blokk + mente;
+        (a[i], mente) = if sum >= BASE {
+            (sum - BASE, 1)
+        } else {
+            (sum, 0)
+        };
+        i += 1;
+    }
+
+    while mente != 0 {
+        let sum = a[i] + mente;
+        (a[i], mente) = if sum >= BASE {
+            (sum - BASE, 1)
+        } else {
+            (sum, 0)
+        };
+        i += 1;
+    }
+}
+
+/// Trekker `b` fra `a`, der `a` må være minst like stor som `b`
+fn trekk_fra(a: &mut [u32], b: &[u32]) {
+    let mut lån = 0;
+
+    for (i, blokk) in a.iter_mut().enumerate() {
+        if i >= b.len() && lån == 0 {
+            break;
         }
 
-        tall
+        let subtrahend = b.get(i).copied().unwrap_or(0) + lån;
+
+        if *blokk < subtrahend {
+            *blokk = *blokk + BASE - subtrahend;
+            lån = 1;
+        } else {
+            *blokk -= subtrahend;
+            lån = 0;
+        }
     }
 }
 
-// Gjør det mulig å printe et Siffer med print!() og println!()-makroene
-impl fmt::Display for Siffer {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.verdi)
+/// Ganger en blokk-vektor med én enkelt blokk
+fn ganger_blokk(a: &[u32], faktor: u32) -> Vec<u32> {
+    let mut produkt = Vec::with_capacity(a.len() + 1);
+    let mut mente = 0;
+
+    for &blokk in a {
+        let verdi = blokk as u64 * faktor as u64 + mente;
+        produkt.push((verdi % BASE as u64) as u32);
+        mente = verdi / BASE as u64;
+    }
+
+    produkt.push(mente as u32);
+    produkt.truncate(lengde_uten_nuller(&produkt));
+    produkt
+}
+
+/// Deler blokkene på `divisor` på stedet, og returnerer resten
+fn del_på(blokker: &mut Vec<u32>, divisor: u32) -> u32 {
+    let mut rest = 0_u64;
+
+    // Resten er under 2^32, så rest * 10^9 + blokk får plass i en u64
+    for blokk in blokker.iter_mut().rev() {
+        let verdi = rest * BASE as u64 + *blokk as u64;
+        *blokk = (verdi / divisor as u64) as u32;
+        rest = verdi % divisor as u64;
+    }
+
+    blokker.truncate(lengde_uten_nuller(blokker));
+    rest as u32
+}
+
+/// Finner hvor mange ganger divisoren går opp i resten, og trekker det fra resten.
+/// Resten må være mindre enn divisor * 10^9, så svaret er alltid én blokk
+fn kvotientblokk(rest: &mut Vec<u32>, divisor: &[u32]) -> u32 {
+    if sammenlign(rest, divisor) == Ordering::Less {
+        return 0;
+    }
+
+    // Anslå svaret fra de øverste blokkene. Med minst to blokker fra divisoren
+    // bommer anslaget med maks et par enere, som rettes opp under
+    let fra = divisor.len().saturating_sub(2);
+    let topp = |blokker: &[u32]| {
+        blokker[fra..]
+            .iter()
+            .rev()
+            .fold(0_u128, |verdi, &blokk| verdi * BASE as u128 + blokk as u128)
+    };
+    let mut antall = u128::min(topp(rest) / topp(divisor), BASE as u128 - 1) as u32;
+
+    let mut produkt = ganger_blokk(divisor, antall);
+    while sammenlign(&produkt, rest) == Ordering::Greater {
+        antall -= 1;
+        trekk_fra(&mut produkt, divisor);
+    }
+
+    trekk_fra(rest, &produkt);
+    rest.truncate(lengde_uten_nuller(rest));
+
+    while sammenlign(rest, divisor) != Ordering::Less {
+        antall += 1;
+        trekk_fra(rest, divisor);
+        rest.truncate(lengde_uten_nuller(rest));
     }
+
+    antall
 }
 
 // Gjør det mulig å printe et Tall med print!() og println!()-makroene
 impl fmt::Display for Tall {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        // Om et Tall ikke har noen Siffer, svar med en tom string
-        if self.er_tomt() {
-            write!(f, "")
-        } else {
-            unsafe {
-                // Opprett en buffer
-                let mut buffer = String::new();
-                let mut siffer = self.første.as_ref().map(|siffer| siffer.as_ref());
-
-                // Iterer gjennom alle Siffer i Tallet
-                loop {
-                    match siffer {
-                        Some(some) => {
-                            // Legg til et Siffer i bufferen (ved hjelp av fmt::Display for Siffer fra ovenfor)
-                            buffer = buffer + format!("{}", some).as_str();
-                            siffer = some.neste.as_ref().map(|neste| neste.as_ref());
-                        }
-                        None => {
-                            break;
-                        }
-                    }
-                }
-
-                write!(f, "{}", buffer)
-            }
+        if self.negativ {
+            write!(f, "-")?;
         }
+
+        // Øverste blokk skrives uten nuller foran, resten fylles ut til 9 siffer (7 blir 000000007)
+        let mut blokker = self.blokker.iter().rev();
+
+        if let Some(øverste) = blokker.next() {
+            write!(f, "{øverste}")?;
+        }
+
+        for blokk in blokker {
+            write!(f, "{blokk:09}")?;
+        }
+
+        Ok(())
     }
 }
 
@@ -153,127 +374,335 @@ impl std::ops::Add<Tall> for Tall {
     type Output = Tall;
 
     fn add(self, rhs: Tall) -> Self::Output {
-        // Opprett et nytt Tall for å oppbevare resultatet av summeringen
-        let mut sum: Tall = Tall::new();
+        // Med likt fortegn legges tallverdiene sammen og fortegnet beholdes
+        let (negativ, rhs_negativ) = (self.negativ, rhs.negativ);
 
-        // Finn ut hvilket tall som er lengst
-        let (lengste, mut korteste) = if self.antall_siffer > rhs.antall_siffer {
-            (self, rhs)
-        } else {
-            (rhs, self)
-        };
+        if negativ == rhs_negativ {
+            return self.sum_absolutt(rhs).med_fortegn(negativ);
+        }
 
-        // Fyll det korteste tallet med 0 til de to tallene har like mange siffer (f.eks. blir 100 + 50 til 100 + 050)
-        while korteste.antall_siffer < lengste.antall_siffer {
-            korteste.sett_inn_foran(0);
+        // Med ulikt fortegn trekkes den minste tallverdien fra den største,
+        // og resultatet får fortegnet til tallet med størst tallverdi
+        match self.sammenlign_absolutt(&rhs) {
+            Ordering::Less => rhs.differanse_absolutt(self).med_fortegn(rhs_negativ),
+            _ => self.differanse_absolutt(rhs).med_fortegn(negativ),
         }
+    }
+}
 
-        let mut lengste_siffer = lengste.siste;
-        let mut korteste_siffer = korteste.siste;
-        let mut verdisum: u32;
-        let mut mente: u32 = 0;
-
-        unsafe {
-            // Iterer gjennom alle sifrene
-            loop {
-                match korteste_siffer {
-                    Some(siffer) => {
-                        // Summer verdiene til sifrene
-                        verdisum = (*siffer.as_ptr()).verdi
-                            + (*lengste_siffer.unwrap().as_ptr()).verdi
-                            + mente;
-
-                        // Om summen av to siffer blir over 10, skriv kun ener-plassen til summen og sett tier-plassen i mente
-                        sum.sett_inn_foran(verdisum % 10);
-                        mente = verdisum / 10;
-
-                        korteste_siffer = (*siffer.as_ptr()).forrige;
-                        lengste_siffer = (*lengste_siffer.unwrap().as_ptr()).forrige;
-                    }
-                    None => {
-                        break;
-                    }
-                }
-            }
+// Implementasjon av subtraksjon-operasjonen for Tall, a - b = a + (-b)
+impl std::ops::Sub<Tall> for Tall {
+    type Output = Tall;
 
-            if mente != 0 {
-                sum.sett_inn_foran(mente);
-            }
+    fn sub(self, rhs: Tall) -> Self::Output {
+        self + -rhs
+    }
+}
+
+// Implementasjon av negasjon for Tall, gir samme tall med motsatt fortegn
+impl std::ops::Neg for Tall {
+    type Output = Tall;
+
+    fn neg(self) -> Self::Output {
+        let negativ = !self.negativ;
+        self.med_fortegn(negativ)
+    }
+}
+
+// Tall sammenlignes etter verdi, slik at f.eks. -5 < 3 < 10
+impl Ord for Tall {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negativ, other.negativ) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.sammenlign_absolutt(other),
+            // For to negative tall er det tallet med minst tallverdi som er størst
+            (true, true) => other.sammenlign_absolutt(self),
         }
+    }
+}
 
-        sum
+impl PartialOrd for Tall {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-// Implementasjon av subtraksjon-operasjonen for Tall
-impl std::ops::Sub<Tall> for Tall {
+impl PartialEq for Tall {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Tall {}
+
+// Implementasjon av multiplikasjon-operasjonen for Tall
+impl std::ops::Mul<Tall> for Tall {
     type Output = Tall;
 
-    fn sub(self, rhs: Tall) -> Self::Output {
-        // Opprett et nytt Tall å oppbevare resultatet i
-        let mut diff = Tall::new();
+    fn mul(self, rhs: Tall) -> Self::Output {
+        self.multipliser(&rhs, KARATSUBA_GRENSE)
+    }
+}
 
-        // Ta en kopi av høyre side (subtrahenden)
-        let mut rhs_kopi = rhs;
+// Implementasjon av divisjon-operasjonen for Tall.
+// Som for heltallstypene krasjer deling på null, bruk del_med_rest for å få en feil i stedet
+impl std::ops::Div<Tall> for Tall {
+    type Output = Tall;
 
-        // Om nødvendig, fyll subtrahenden med 0 til de to numrene er like lange (100 - 50 bilr 100 - 050)
-        while rhs_kopi.antall_siffer < self.antall_siffer {
-            rhs_kopi.sett_inn_foran(0);
+    fn div(self, rhs: Tall) -> Self::Output {
+        match self.del_med_rest(&rhs) {
+            Ok((kvotient, _)) => kvotient,
+            Err(melding) => panic!("{melding}"),
         }
+    }
+}
 
-        let mut verdidiff: i32;
-        let mut lån = 0;
-        let mut minuend_siffer = self.siste;
-        let mut subtrahend_siffer = rhs_kopi.siste;
-
-        unsafe {
-            // Iterer gjennom alle sifrene
-            loop {
-                match minuend_siffer {
-                    Some(minuend) => {
-                        match subtrahend_siffer {
-                            Some(subtrahend) => {
-                                // Trekk de to sifrene på samme plass fra hverandre
-                                // (her må det omformes litt da sifrenes verdi er unsigned men differansen kan bli negativ)
-                                verdidiff = (*minuend.as_ptr()).verdi as i32
-                                    - (*subtrahend.as_ptr()).verdi as i32
-                                    - lån;
-
-                                // Hvis differansen mellom to sifre er negativ, lån fra neste siffer
-                                if verdidiff < 0 {
-                                    lån = 1;
-                                    diff.sett_inn_foran((10 + verdidiff) as u32);
-                                } else {
-                                    lån = 0;
-                                    diff.sett_inn_foran(verdidiff as u32);
-                                }
-
-                                subtrahend_siffer = (*subtrahend.as_ptr()).forrige;
-                            }
-                            None => {
-                                break;
-                            }
-                        }
-
-                        minuend_siffer = (*minuend.as_ptr()).forrige;
-                    }
-                    None => {
-                        break;
-                    }
-                }
-            }
+// Implementasjon av rest-operasjonen (modulo) for Tall, som også krasjer ved deling på null
+impl std::ops::Rem<Tall> for Tall {
+    type Output = Tall;
+
+    fn rem(self, rhs: Tall) -> Self::Output {
+        match self.del_med_rest(&rhs) {
+            Ok((_, rest)) => rest,
+            Err(melding) => panic!("{melding}"),
         }
+    }
+}
+
+/// Måler tiden for de vanligste operasjonene på to tilfeldige tall med `siffer` siffer hver
+fn benchmark(siffer: usize, karatsuba_grense: usize) {
+    // Enkel xorshift med frø fra klokken, det holder for å lage testdata
+    let mut tilstand = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|tid| tid.as_nanos() as u64)
+        .unwrap_or(0)
+        | 1;
+    let mut tilfeldig_tall = |siffer: usize| -> String {
+        (0..siffer)
+            .map(|i| {
+                tilstand ^= tilstand << 13;
+                tilstand ^= tilstand >> 7;
+                tilstand ^= tilstand << 17;
+                // Første siffer er aldri 0, så tallet får akkurat `siffer` siffer
+                let verdi = if i == 0 {
+                    1 + tilstand % 9
+                } else {
+                    tilstand % 10
+                };
+                char::from(b'0' + verdi as u8)
+            })
+            .collect()
+    };
 
-        diff
+    let siffer = usize::max(siffer, 1);
+    let (streng1, streng2) = (tilfeldig_tall(siffer), tilfeldig_tall(siffer));
+    let divisor = tilfeldig_tall(usize::max(siffer / 100, 1));
+
+    // Kjører en operasjon og skriver ut tiden den brukte
+    fn mål<T>(navn: &str, operasjon: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let resultat = operasjon();
+        println!(
+            "{navn:<28} {:>12.3} ms",
+            start.elapsed().as_secs_f64() * 1000.0
+        );
+        resultat
     }
+
+    println!("To tall med {siffer} siffer hver\n");
+
+    let tall1 = mål("fra_streng", || Tall::fra_streng(streng1));
+    let tall2 = Tall::fra_streng(streng2);
+    let divisor = Tall::fra_streng(divisor);
+
+    let sum = mål("+", || tall1.clone() + tall2.clone());
+    mål("-", || tall1.clone() - tall2.clone());
+    mål("*", || tall1.multipliser(&tall2, karatsuba_grense));
+    let _ = mål(&format!("/ ({} siffer)", divisor.to_string().len()), || {
+        tall1.del_med_rest(&divisor)
+    });
+    mål("Display", || sum.to_string());
+}
+
+/// Regner ut et regnestykke med desimaltall, og skriver det ut oppstilt med kommaene under hverandre
+fn regn_ut_desimalt(
+    tall1: &str,
+    operator: &str,
+    tall2: &str,
+    desimaler: Option<u32>,
+    avrunding: Avrunding,
+    karatsuba_grense: usize,
+) {
+    let tall1: Desimaltall = tall1.parse().unwrap();
+    let tall2: Desimaltall = tall2.parse().unwrap();
+
+    let resultat = match operator {
+        "+" => Ok(tall1.clone() + tall2.clone()),
+        "-" => Ok(tall1.clone() - tall2.clone()),
+        "*" => Ok(tall1.multipliser(&tall2, karatsuba_grense)),
+        _ => tall1.del(
+            &tall2,
+            desimaler.unwrap_or(DESIMALER),
+            avrunding,
+            karatsuba_grense,
+        ),
+    };
+
+    let resultat = match resultat {
+        Ok(resultat) => resultat,
+        Err(melding) => {
+            println!("{melding}");
+            exit(1);
+        }
+    };
+
+    // Er antall desimaler angitt rundes også svar på + - og * av til så mange desimaler
+    let resultat = match desimaler {
+        Some(desimaler) => resultat.avrund(desimaler, avrunding, karatsuba_grense),
+        None => resultat,
+    };
+
+    // Fyll ut med mellomrom til høyre for tall med færre desimaler, så kommaene havner under hverandre
+    let flest_desimaler = [&tall1, &tall2, &resultat]
+        .iter()
+        .map(|tall| tall.skala() as usize)
+        .max()
+        .unwrap();
+
+    let [tall1, tall2, resultat] = [&tall1, &tall2, &resultat].map(|tall| {
+        let mut tekst = tall.to_string();
+        let mangler = flest_desimaler - tall.skala() as usize;
+        // Uten desimaler mangler også selve kommaet
+        let mangler = if tall.skala() == 0 && flest_desimaler > 0 {
+            mangler + 1
+        } else {
+            mangler
+        };
+        tekst.push_str(&" ".repeat(mangler));
+        tekst
+    });
+
+    let lengde = usize::max(usize::max(tall1.len(), tall2.len()), resultat.len());
+
+    println!("  {:>lengde$}", tall1);
+    println!("{operator} {:>lengde$}", tall2);
+    println!("= {:>lengde$}", resultat);
 }
 
 fn main() {
     // Les argumenter fra terminalen
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
 
     // Lag en string med info til utskrift om antatt brukerfeil oppdages
-    let hjelp = format!("Forventet bruk: {} [tall] [+/-] [tall]", args[0]);
+    let hjelp = format!(
+        "Forventet bruk: {} [valg] [tall] [+ - * / %] [tall]
+(* må stå i anførselstegn i de fleste skall)
+Eller: {0} [valg] \"uttrykk med + - * / % ^ og parenteser\"
+Eller: {0} [valg] --repl
+Eller: {0} [valg] --benchmark [siffer]
+Valg:
+\t--karatsuba siffer   Antall siffer der multiplikasjon går over til Karatsuba (standard {standard})
+\t--vis                Vis hele oppstillingen for + - og *, med mente, lån og delprodukter
+\t--desimaler n        Antall desimaler i svar med desimaltall som 12.50. Standard er eksakt svar,
+\t                     og 10 desimaler ved deling
+\t--avrunding a        Avrunding ved deling av desimaltall: halv-par (standard), halv-opp eller kutt
+\t--utskrift u         desimal (standard), tusenskille, vitenskapelig, 2, 8, 16 eller 36
+Tall kan skrives med prefiks 0b, 0o eller 0x for grunntall 2, 8 og 16, eller som grunntall#sifre
+for grunntall fra 2 til 36, som 36#zz
+{}",
+        args[0],
+        uttrykk::funksjonsliste(),
+        standard = KARATSUBA_GRENSE * SIFFER_PER_BLOKK
+    );
+
+    // Plukk ut valgfri grense for når multiplikasjon skal bruke Karatsuba. Grensen angis i siffer,
+    // men multiplikasjonen regner i blokker, så den rundes opp til et helt antall blokker
+    let mut karatsuba_grense = KARATSUBA_GRENSE;
+    if let Some(indeks) = args.iter().position(|arg| arg == "--karatsuba") {
+        karatsuba_grense = match args.get(indeks + 1).map(|grense| grense.parse::<usize>()) {
+            Some(Ok(siffer)) => siffer.div_ceil(SIFFER_PER_BLOKK),
+            _ => {
+                println!("Ugyldig grense for Karatsuba");
+                println!("{}", hjelp);
+                return;
+            }
+        };
+        args.drain(indeks..indeks + 2);
+    }
+
+    // Plukk ut valgfri utskrift av svaret
+    let mut utskrift = Utskrift::Desimal;
+    if let Some(indeks) = args.iter().position(|arg| arg == "--utskrift") {
+        utskrift = match args
+            .get(indeks + 1)
+            .and_then(|navn| Utskrift::fra_navn(navn))
+        {
+            Some(utskrift) => utskrift,
+            None => {
+                println!("Ugyldig utskrift");
+                println!("{}", hjelp);
+                return;
+            }
+        };
+        args.drain(indeks..indeks + 2);
+    }
+
+    // Plukk ut valg om å vise hele oppstillingen
+    let vis = match args.iter().position(|arg| arg == "--vis") {
+        Some(indeks) => {
+            args.remove(indeks);
+            true
+        }
+        None => false,
+    };
+
+    // Plukk ut valgfritt antall desimaler og avrunding for deling av desimaltall
+    let mut desimaler = None;
+    if let Some(indeks) = args.iter().position(|arg| arg == "--desimaler") {
+        desimaler = match args.get(indeks + 1).map(|antall| antall.parse()) {
+            Some(Ok(antall)) => Some(antall),
+            _ => {
+                println!("Ugyldig antall desimaler");
+                println!("{}", hjelp);
+                return;
+            }
+        };
+        args.drain(indeks..indeks + 2);
+    }
+
+    let mut avrunding = Avrunding::HalvPar;
+    if let Some(indeks) = args.iter().position(|arg| arg == "--avrunding") {
+        avrunding = match args
+            .get(indeks + 1)
+            .and_then(|navn| Avrunding::fra_navn(navn))
+        {
+            Some(avrunding) => avrunding,
+            None => {
+                println!("Ugyldig avrunding");
+                println!("{}", hjelp);
+                return;
+            }
+        };
+        args.drain(indeks..indeks + 2);
+    }
+
+    if args.get(1).map(String::as_str) == Some("--repl") {
+        uttrykk::repl(karatsuba_grense, utskrift);
+        return;
+    }
+
+    if args.get(1).map(String::as_str) == Some("--benchmark") {
+        match args.get(2).map(|siffer| siffer.parse()) {
+            None => benchmark(1_000_000, karatsuba_grense),
+            Some(Ok(siffer)) => benchmark(siffer, karatsuba_grense),
+            Some(Err(_)) => {
+                println!("Ugyldig antall siffer for benchmark");
+                println!("{}", hjelp);
+            }
+        }
+        return;
+    }
 
     // Sjekk antall argumenter, om ingen er oppgitt så skriv ut en enkel forklaring
     if args.len() <= 1 {
@@ -282,57 +711,243 @@ fn main() {
         return;
     }
 
-    // Les første tall fra terminalen og konstruer et Tall-objekt
-    let tall1 = match args.get(1) {
-        Some(tall) => Tall::fra_streng(tall.to_string()),
-        None => {
-            println!("uffda");
-            return;
-        }
-    };
+    // Regnestykker med desimaltall regnes ut eksakt, bortsett fra deling som gir angitt antall desimaler
+    let er_desimaltall = |arg: &str| arg.parse::<Desimaltall>().is_ok();
+
+    if args.len() == 4
+        && (args[1].contains('.') || args[3].contains('.') || desimaler.is_some())
+        && er_desimaltall(&args[1])
+        && er_desimaltall(&args[3])
+        && ["+", "-", "*", "/"].contains(&args[2].as_str())
+    {
+        regn_ut_desimalt(
+            &args[1],
+            &args[2],
+            &args[3],
+            desimaler,
+            avrunding,
+            karatsuba_grense,
+        );
+        return;
+    }
 
-    // Les operatoren fra terminalen
-    let operator = match args.get(2) {
-        Some(symbol) => symbol,
-        None => {
-            println!("Ingen operator angitt");
-            println!("{}", hjelp);
-            return;
-        }
+    // Et regnestykke på formen tall operator tall skrives ut oppstilt. Alt annet regnes ut som ett uttrykk,
+    // der argumentene slås sammen med mellomrom, slik at både "2 * (3 + 4)" og 2 '*' '(3' + '4)' fungerer
+    let er_tall = |arg: &str| {
+        let sifre = arg.strip_prefix('-').unwrap_or(arg);
+        !sifre.is_empty() && sifre.chars().all(|bokstav| bokstav.is_ascii_digit())
     };
 
-    // Avgjør hvilken matematisk operasjon som skal utføres ut fra operatoren
-    let operasjon = match operator.as_str() {
-        "+" => Tall::add,
-        "-" => Tall::sub,
-        _ => {
-            println!("Ukjent operator \"{operator}\"");
-            println!("{}", hjelp);
-            return;
+    if args.len() != 4
+        || !er_tall(&args[1])
+        || !er_tall(&args[3])
+        || !["+", "-", "*", "/", "%"].contains(&args[2].as_str())
+    {
+        match uttrykk::regn_ut(&args[1..].join(" "), karatsuba_grense) {
+            Ok(svar) => println!("{}", utskrift.formater(&svar)),
+            Err(melding) => {
+                println!("{melding}");
+                exit(1);
+            }
         }
+        return;
+    }
+
+    let tall1 = Tall::fra_streng(args[1].clone());
+    let operator = &args[2];
+    let tall2 = Tall::fra_streng(args[3].clone());
+
+    // Utfør utregning. Operatorene tar eierskap til operandene, så de klones for å kunne skrives ut etterpå
+    let resultat = match operator.as_str() {
+        "+" => Ok(tall1.clone() + tall2.clone()),
+        "-" => Ok(tall1.clone() - tall2.clone()),
+        "*" => Ok(tall1.multipliser(&tall2, karatsuba_grense)),
+        "/" => tall1.del_med_rest(&tall2).map(|(kvotient, _)| kvotient),
+        _ => tall1.del_med_rest(&tall2).map(|(_, rest)| rest),
     };
 
-    // Les andre tall fra terminalen og konstruer dets Tall-objekt
-    let tall2 = match args.get(3) {
-        Some(tall) => Tall::fra_streng(tall.clone()),
-        None => {
-            println!("Tall mangler!");
-            println!("{}", hjelp);
-            return;
+    let resultat = match resultat {
+        Ok(resultat) => resultat,
+        Err(melding) => {
+            println!("{melding}");
+            exit(1);
         }
     };
 
-    // Utfør utregning
-    let resultat = operasjon(tall1, tall2);
+    if vis {
+        match oppstilling::oppstilling(&tall1, operator, &tall2, &resultat) {
+            Some(oppstilling) => {
+                print!("{oppstilling}");
+                return;
+            }
+            None => println!("Det finnes ingen oppstilling for {operator}, viser bare svaret"),
+        }
+    }
 
-    // Finn ut hvor bred utskriften må være for å romme alle tallene i bredden
-    let lengde = usize::max(
-        usize::max(tall1.antall_siffer, tall2.antall_siffer),
-        resultat.antall_siffer,
+    let (tall1, tall2, resultat) = (
+        utskrift.formater(&tall1),
+        utskrift.formater(&tall2),
+        utskrift.formater(&resultat),
     );
 
+    // Finn ut hvor bred utskriften må være for å romme alle tallene i bredden, inkludert fortegn
+    let lengde = usize::max(usize::max(tall1.len(), tall2.len()), resultat.len());
+
     // Skriv ut resultatet av utregningen
-    println!("  {:>lengde$}", format!("{}", tall1));
-    println!("{operator} {:>lengde$}", format!("{}", tall2));
-    println!("= {:>lengde$}", format!("{}", resultat));
+    println!("  {:>lengde$}", tall1);
+    println!("{operator} {:>lengde$}", tall2);
+    println!("= {:>lengde$}", resultat);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tall(streng: &str) -> Tall {
+        streng.parse().unwrap()
+    }
+
+    #[test]
+    fn klone_er_uavhengig_av_originalen() {
+        let original = tall("123");
+        let mut klone = original.clone();
+        klone.blokker[0] = 1234;
+
+        assert_eq!(original.to_string(), "123");
+        assert_eq!(klone.to_string(), "1234");
+    }
+
+    #[test]
+    fn addisjon_og_subtraksjon_endrer_ikke_operandene() {
+        let kort = tall("5");
+        let lang = tall("12345");
+
+        assert_eq!((kort.clone() + lang.clone()).to_string(), "12350");
+        assert_eq!((kort.clone() - lang.clone()).to_string(), "-12340");
+        assert_eq!(kort.to_string(), "5");
+        assert_eq!(lang.to_string(), "12345");
+    }
+
+    #[test]
+    fn nuller_foran_fjernes() {
+        assert_eq!(tall("000120").to_string(), "120");
+        assert_eq!(tall("-000").to_string(), "0");
+        assert!(!tall("-0").negativ);
+    }
+
+    #[test]
+    fn multiplikasjon_med_og_uten_karatsuba() {
+        let a = tall("-98765432109876543210");
+        let b = tall("12345678901234567890");
+
+        assert_eq!(a.multipliser(&b, 2), a.multipliser(&b, 100));
+        assert_eq!(
+            a.multipliser(&b, 2),
+            tall("-1219326311370217952237463801111263526900")
+        );
+    }
+
+    #[test]
+    fn divisjon_og_rest_rundes_mot_null() {
+        assert_eq!(
+            tall("-17").del_med_rest(&tall("5")),
+            Ok((tall("-3"), tall("-2")))
+        );
+        assert_eq!(
+            tall("17").del_med_rest(&tall("-5")),
+            Ok((tall("-3"), tall("2")))
+        );
+    }
+
+    #[test]
+    fn deling_på_null_gir_feil() {
+        assert!(tall("1").del_med_rest(&tall("0")).is_err());
+    }
+
+    #[test]
+    fn divisjon_og_rest_som_operatorer() {
+        assert_eq!(tall("-17") / tall("5"), tall("-3"));
+        assert_eq!(tall("-17") % tall("5"), tall("-2"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Kan ikke dele på null")]
+    fn divisjonsoperator_krasjer_ved_deling_på_null() {
+        let _ = tall("1") / tall("0");
+    }
+
+    #[test]
+    #[should_panic(expected = "Kan ikke dele på null")]
+    fn restoperator_krasjer_ved_deling_på_null() {
+        let _ = tall("1") % tall("0");
+    }
+
+    #[test]
+    fn sammenligning() {
+        assert!(tall("-10") < tall("-9"));
+        assert!(tall("-9") < tall("0"));
+        assert!(tall("0") < tall("9"));
+        assert!(tall("9") < tall("1000000000"));
+    }
+
+    #[test]
+    fn gjentatt_addisjon_av_kloner() {
+        let mut akkumulator = tall("1");
+        for _ in 0..20 {
+            akkumulator = akkumulator.clone() + akkumulator;
+        }
+
+        assert_eq!(akkumulator, tall("1048576"));
+    }
+
+    #[test]
+    fn lagres_i_blokker_på_9_siffer() {
+        assert_eq!(tall("12345678901").blokker, [345678901, 12]);
+        assert_eq!(tall("1000000000").blokker, [0, 1]);
+        assert_eq!(tall("0").blokker, [0]);
+    }
+
+    #[test]
+    fn mente_og_lån_mellom_blokker() {
+        assert_eq!(tall("999999999") + tall("1"), tall("1000000000"));
+        assert_eq!(
+            tall("999999999999999999") + tall("1"),
+            tall("1000000000000000000")
+        );
+        assert_eq!(
+            tall("1000000000000000000") - tall("1"),
+            tall("999999999999999999")
+        );
+        assert_eq!(tall("1000000000") - tall("1000000000"), tall("0"));
+    }
+
+    #[test]
+    fn blokker_under_øverste_fylles_ut_med_nuller() {
+        assert_eq!(
+            tall("-1000000007000000000").to_string(),
+            "-1000000007000000000"
+        );
+    }
+
+    #[test]
+    fn divisjon_med_divisor_over_flere_blokker() {
+        assert_eq!(
+            tall("123456789012345678901234567890").del_med_rest(&tall("9876543210987654321")),
+            Ok((tall("12499999886"), tall("925925941327160484")))
+        );
+        assert_eq!(
+            tall("1000000000000000000000000000").del_med_rest(&tall("1000000000000000000")),
+            Ok((tall("1000000000"), tall("0")))
+        );
+    }
+
+    #[test]
+    fn karatsuba_over_mange_blokker() {
+        // 10^90 - 1 i andre er 10^180 - 2 * 10^90 + 1, og tar 10 blokker per faktor
+        let nitall = tall(&"9".repeat(90));
+        let forventet = format!("{}8{}1", "9".repeat(89), "0".repeat(89));
+
+        assert_eq!(nitall.multipliser(&nitall, 2).to_string(), forventet);
+        assert_eq!(nitall.multipliser(&nitall, 100).to_string(), forventet);
+    }
 }
@@ -0,0 +1,301 @@
+use std::{cmp::Ordering, fmt::Display};
+
+use crate::Node;
+
+/// Mengdeoperasjoner mellom to søketrær.
+///
+/// Alle operasjonene fletter sammen de sorterte verdiene fra de to trærne i én gjennomgang,
+/// og bygger et nytt balansert tre fra resultatet, så de går i lineær tid.
+/// For multimengder brukes antallet på hver node: union tar det største antallet,
+/// snitt det minste, og differansene trekker antallene fra hverandre
+impl<T: Ord + Display + Clone> Node<T> {
+    /// Alle verdier som finnes i minst ett av trærne
+    pub fn union(&self, annen: &Self) -> Self {
+        Self::flett(self, annen, usize::max)
+    }
+
+    /// Verdiene som finnes i begge trærne
+    pub fn snitt(&self, annen: &Self) -> Self {
+        Self::flett(self, annen, usize::min)
+    }
+
+    /// Verdiene i dette treet som ikke finnes i det andre
+    pub fn differanse(&self, annen: &Self) -> Self {
+        Self::flett(self, annen, usize::saturating_sub)
+    }
+
+    /// Verdiene som finnes i nøyaktig ett av trærne
+    pub fn symmetrisk_differanse(&self, annen: &Self) -> Self {
+        Self::flett(self, annen, usize::abs_diff)
+    }
+
+    /// Sjekker om alle verdiene i dette treet også finnes i det andre treet
+    pub fn er_delmengde(&self, annen: &Self) -> bool {
+        self.differanse(annen).er_tom()
+    }
+
+    /// Bygger et balansert tre fra en sortert liste med verdier og antall,
+    /// ved å bruke midterste verdi som rot og bygge under-trærne rekursivt fra hver halvdel
+    pub fn fra_sortert(liste: &[(&T, usize)]) -> Self {
+        if liste.is_empty() {
+            return Self::Tom;
+        }
+
+        let midt = liste.len() / 2;
+        let (verdi, antall) = liste[midt];
+
+        Self::Node {
+            verdi: verdi.clone(),
+            antall,
+            venstre: Box::new(Self::fra_sortert(&liste[..midt])),
+            høyre: Box::new(Self::fra_sortert(&liste[midt + 1..])),
+        }
+    }
+
+    /// Legger verdiene i treet inn i bufferen i sortert rekkefølge, uten å kopiere dem
+    fn samle<'a>(&'a self, buffer: &mut Vec<(&'a T, usize)>) {
+        if let Self::Node {
+            verdi,
+            antall,
+            venstre,
+            høyre,
+        } = self
+        {
+            venstre.samle(buffer);
+            buffer.push((verdi, *antall));
+            høyre.samle(buffer);
+        }
+    }
+
+    /// Fletter sammen verdiene fra to trær, der `regel` avgjør antallet i resultatet
+    /// ut fra antallet i hvert av trærne (0 om verdien mangler). Verdier som får antall 0 utelates
+    fn flett(a: &Self, b: &Self, regel: fn(usize, usize) -> usize) -> Self {
+        let mut liste_a = Vec::new();
+        let mut liste_b = Vec::new();
+        a.samle(&mut liste_a);
+        b.samle(&mut liste_b);
+
+        let mut resultat: Vec<(&T, usize)> = Vec::with_capacity(liste_a.len() + liste_b.len());
+        let (mut i, mut j) = (0, 0);
+
+        while i < liste_a.len() || j < liste_b.len() {
+            // Når en av listene er brukt opp tas resten fra den andre
+            let retning = match (liste_a.get(i), liste_b.get(j)) {
+                (Some((verdi_a, _)), Some((verdi_b, _))) => verdi_a.cmp(verdi_b),
+                (Some(_), None) => Ordering::Less,
+                _ => Ordering::Greater,
+            };
+
+            let (verdi, antall) = match retning {
+                Ordering::Less => {
+                    let (verdi, antall_a) = liste_a[i];
+                    i += 1;
+                    (verdi, regel(antall_a, 0))
+                }
+                Ordering::Greater => {
+                    let (verdi, antall_b) = liste_b[j];
+                    j += 1;
+                    (verdi, regel(0, antall_b))
+                }
+                Ordering::Equal => {
+                    let ((verdi, antall_a), (_, antall_b)) = (liste_a[i], liste_b[j]);
+                    i += 1;
+                    j += 1;
+                    (verdi, regel(antall_a, antall_b))
+                }
+            };
+
+            if antall > 0 {
+                resultat.push((verdi, antall));
+            }
+        }
+
+        Self::fra_sortert(&resultat)
+    }
+}
+
+/// To trær er like om de inneholder de samme verdiene med samme antall, uavhengig av formen på trærne
+impl<T: Ord + Display + Clone> PartialEq for Node<T> {
+    fn eq(&self, annen: &Self) -> bool {
+        let mut liste_a = Vec::new();
+        let mut liste_b = Vec::new();
+        self.samle(&mut liste_a);
+        annen.samle(&mut liste_b);
+
+        liste_a == liste_b
+    }
+}
+
+impl<T: Ord + Display + Clone> Eq for Node<T> {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    /// Bygger en multimengde, der hver verdi telles like mange ganger som den står i lista
+    fn multimengde(verdier: &[u32]) -> Node<u32> {
+        let mut tre = Node::ny_tom();
+        for &verdi in verdier {
+            tre.sett_inn_telt(verdi);
+        }
+        tre
+    }
+
+    /// Regner ut forventet resultat med en `BTreeMap` som teller forekomstene
+    fn fasit(a: &[u32], b: &[u32], regel: fn(usize, usize) -> usize) -> Vec<(u32, usize)> {
+        let mut antall = BTreeMap::<u32, (usize, usize)>::new();
+        for &verdi in a {
+            antall.entry(verdi).or_default().0 += 1;
+        }
+        for &verdi in b {
+            antall.entry(verdi).or_default().1 += 1;
+        }
+
+        antall
+            .into_iter()
+            .map(|(verdi, (antall_a, antall_b))| (verdi, regel(antall_a, antall_b)))
+            .filter(|&(_, antall)| antall > 0)
+            .collect()
+    }
+
+    /// Sjekker at høyden på under-trærne til hver node skiller seg med maks 1
+    fn er_balansert(tre: &Node<u32>) -> bool {
+        match tre {
+            Node::Tom => true,
+            Node::Node {
+                venstre, høyre, ..
+            } => {
+                venstre.høyde().abs_diff(høyre.høyde()) <= 1
+                    && er_balansert(venstre)
+                    && er_balansert(høyre)
+            }
+        }
+    }
+
+    const PAR: [(&[u32], &[u32]); 6] = [
+        // Overlappende, med ulikt antall på begge sider
+        (&[1, 2, 2, 3, 3, 3, 5], &[2, 3, 3, 4, 4, 5, 5]),
+        // Uten felles verdier
+        (&[1, 3, 5], &[2, 4, 6]),
+        // Den ene eller begge tomme
+        (&[], &[1, 1, 2]),
+        (&[7, 7], &[]),
+        (&[], &[]),
+        // Like mengder
+        (&[4, 8, 8], &[8, 4, 8]),
+    ];
+
+    #[test]
+    fn operasjonene_følger_telleregelene() {
+        for (a, b) in PAR {
+            let (tre_a, tre_b) = (multimengde(a), multimengde(b));
+
+            assert_eq!(
+                tre_a.union(&tre_b).som_telt_liste(),
+                fasit(a, b, usize::max),
+                "{a:?} ∪ {b:?}"
+            );
+            assert_eq!(
+                tre_a.snitt(&tre_b).som_telt_liste(),
+                fasit(a, b, usize::min),
+                "{a:?} ∩ {b:?}"
+            );
+            assert_eq!(
+                tre_a.differanse(&tre_b).som_telt_liste(),
+                fasit(a, b, usize::saturating_sub),
+                "{a:?} - {b:?}"
+            );
+            assert_eq!(
+                tre_a.symmetrisk_differanse(&tre_b).som_telt_liste(),
+                fasit(a, b, usize::abs_diff),
+                "{a:?} Δ {b:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn antall_ved_overlapp() {
+        let a = multimengde(&[1, 2, 2, 3, 3, 3]);
+        let b = multimengde(&[2, 3, 3, 3, 3, 4]);
+
+        assert_eq!(
+            a.union(&b).som_telt_liste(),
+            [(1, 1), (2, 2), (3, 4), (4, 1)]
+        );
+        assert_eq!(a.snitt(&b).som_telt_liste(), [(2, 1), (3, 3)]);
+        assert_eq!(a.differanse(&b).som_telt_liste(), [(1, 1), (2, 1)]);
+        assert_eq!(b.differanse(&a).som_telt_liste(), [(3, 1), (4, 1)]);
+        assert_eq!(
+            a.symmetrisk_differanse(&b).som_telt_liste(),
+            [(1, 1), (2, 1), (3, 1), (4, 1)]
+        );
+    }
+
+    #[test]
+    fn delmengde_tar_hensyn_til_antall() {
+        let a = multimengde(&[1, 2]);
+        let b = multimengde(&[1, 2, 2, 3]);
+
+        assert!(a.er_delmengde(&b));
+        assert!(!b.er_delmengde(&a));
+        assert!(a.er_delmengde(&a));
+        assert!(Node::ny_tom().er_delmengde(&a));
+        assert!(!a.er_delmengde(&Node::ny_tom()));
+
+        // To forekomster er ikke en delmengde av én
+        assert!(!multimengde(&[2, 2]).er_delmengde(&multimengde(&[1, 2])));
+    }
+
+    #[test]
+    fn likhet_avhenger_ikke_av_formen() {
+        // Samme verdier satt inn i ulik rekkefølge gir trær med ulik form
+        let a = multimengde(&[1, 2, 3, 4]);
+        let b = multimengde(&[3, 1, 4, 2]);
+        assert_ne!(a.høyde(), b.høyde());
+        assert!(a == b);
+
+        assert!(a != multimengde(&[1, 2, 3]));
+        assert!(a != multimengde(&[1, 2, 3, 4, 4]));
+        assert!(multimengde(&[]) == Node::ny_tom());
+    }
+
+    #[test]
+    fn flett_utelater_verdier_med_antall_null() {
+        let a = multimengde(&[1, 2, 3]);
+        let b = multimengde(&[2, 3, 4]);
+
+        assert_eq!(Node::flett(&a, &b, |_, _| 0).som_telt_liste(), []);
+        assert_eq!(
+            Node::flett(&a, &b, |a, b| a * 10 + b).som_telt_liste(),
+            [(1, 10), (2, 11), (3, 11), (4, 1)]
+        );
+    }
+
+    #[test]
+    fn fra_sortert_gir_balansert_tre() {
+        for lengde in 0..100u32 {
+            let verdier: Vec<u32> = (0..lengde).collect();
+            let liste: Vec<(&u32, usize)> = verdier.iter().map(|verdi| (verdi, 1)).collect();
+            let tre = Node::fra_sortert(&liste);
+
+            assert_eq!(tre.som_liste(), verdier);
+            assert!(er_balansert(&tre));
+            // Et balansert tre med n verdier har høyde ⌈log2(n + 1)⌉
+            assert_eq!(tre.høyde(), (u32::BITS - lengde.leading_zeros()) as usize);
+        }
+    }
+
+    #[test]
+    fn resultatet_er_balansert() {
+        // Sortert innsetting gir et tre som er en lang kjede, men resultatet bygges på nytt
+        let a = multimengde(&(0..64).collect::<Vec<_>>());
+        let b = multimengde(&(32..96).collect::<Vec<_>>());
+        assert_eq!(a.høyde(), 64);
+
+        let union = a.union(&b);
+        assert_eq!(union.høyde(), 7);
+        assert!(er_balansert(&union));
+    }
+}
@@ -0,0 +1,270 @@
+use crate::Tall;
+
+/// En linje i oppstillingen: tegnet i venstre marg, sifrene, og en forklaring til høyre
+struct Linje {
+    marg: &'static str,
+    innhold: String,
+    forklaring: String,
+}
+
+impl Linje {
+    fn ny(marg: &'static str, innhold: String, forklaring: &str) -> Self {
+        Self {
+            marg,
+            innhold,
+            forklaring: forklaring.to_string(),
+        }
+    }
+
+    /// Skillelinjen over en sum, tegnes som en strek over hele bredden
+    fn skille() -> Self {
+        Self::ny("", String::new(), "")
+    }
+}
+
+/// Sifrene i tallverdien, med minst signifikante siffer først
+fn sifre(tall: &Tall) -> Vec<u32> {
+    tall.til_grunntall(10)
+        .bytes()
+        .rev()
+        .map(|siffer| (siffer - b'0') as u32)
+        .collect()
+}
+
+/// Lager en rad med mente eller lån over sifrene: "1" over kolonnene som er merket, ellers mellomrom.
+/// Kolonnene er minst signifikante først, og raden skrives med mest signifikante kolonne først
+fn merkerad(merker: &[bool]) -> String {
+    merker
+        .iter()
+        .rev()
+        .map(|&merket| if merket { '1' } else { ' ' })
+        .collect()
+}
+
+/// Skolebok-addisjon av to tallverdier, med mente ført over kolonnen den går til
+fn addisjon(a: &Tall, b: &Tall, svar: &Tall, linjer: &mut Vec<Linje>) {
+    let (sifre_a, sifre_b) = (sifre(a), sifre(b));
+    let lengde = usize::max(sifre_a.len(), sifre_b.len());
+
+    let mut mente = vec![false; lengde + 1];
+    for i in 0..lengde {
+        let sum = sifre_a.get(i).unwrap_or(&0) + sifre_b.get(i).unwrap_or(&0) + mente[i] as u32;
+        mente[i + 1] = sum >= 10;
+    }
+
+    if mente.contains(&true) {
+        linjer.push(Linje::ny("  ", merkerad(&mente), "mente"));
+    }
+    linjer.push(Linje::ny("  ", a.abs().to_string(), ""));
+    linjer.push(Linje::ny("+ ", b.abs().to_string(), ""));
+    linjer.push(Linje::skille());
+    linjer.push(Linje::ny("= ", svar.to_string(), ""));
+}
+
+/// Skolebok-subtraksjon av to tallverdier, der `a` er størst. Lånet merkes over kolonnen det lånes fra
+fn subtraksjon(a: &Tall, b: &Tall, svar: &Tall, linjer: &mut Vec<Linje>) {
+    let (sifre_a, sifre_b) = (sifre(a), sifre(b));
+
+    // lån[i] betyr at kolonne i har lånt bort 1 til kolonnen til høyre for seg
+    let mut lån = vec![false; sifre_a.len() + 1];
+    for i in 0..sifre_a.len() {
+        let subtrahend = sifre_b.get(i).unwrap_or(&0) + lån[i] as u32;
+        lån[i + 1] = sifre_a[i] < subtrahend;
+    }
+
+    if lån.contains(&true) {
+        linjer.push(Linje::ny(
+            "  ",
+            merkerad(&lån[..sifre_a.len()]),
+            "lån, 1 lånes bort til kolonnen til høyre",
+        ));
+    }
+    linjer.push(Linje::ny("  ", a.abs().to_string(), ""));
+    linjer.push(Linje::ny("- ", b.abs().to_string(), ""));
+    linjer.push(Linje::skille());
+    linjer.push(Linje::ny("= ", svar.to_string(), ""));
+}
+
+/// Skolebok-multiplikasjon: én rad per siffer i `b`, flyttet like mange plasser som sifferet står fra høyre
+fn multiplikasjon(a: &Tall, b: &Tall, svar: &Tall, linjer: &mut Vec<Linje>) {
+    let a = a.abs();
+    let sifre_b = sifre(b);
+
+    linjer.push(Linje::ny("  ", a.to_string(), ""));
+    linjer.push(Linje::ny("* ", b.abs().to_string(), ""));
+
+    if sifre_b.len() > 1 {
+        linjer.push(Linje::skille());
+
+        for (plass, &siffer) in sifre_b.iter().enumerate() {
+            let delprodukt = a.clone() * Tall::from(siffer as u64);
+            let forklaring = match plass {
+                0 => format!("{a} * {siffer}"),
+                1 => format!("{a} * {siffer}, flyttet 1 plass"),
+                _ => format!("{a} * {siffer}, flyttet {plass} plasser"),
+            };
+
+            linjer.push(Linje {
+                marg: if plass == 0 { "  " } else { "+ " },
+                innhold: format!("{delprodukt}{}", " ".repeat(plass)),
+                forklaring,
+            });
+        }
+    }
+
+    linjer.push(Linje::skille());
+    linjer.push(Linje::ny("= ", svar.to_string(), ""));
+}
+
+/// Stiller opp et regnestykke slik det regnes for hånd, med mente- og lånerader for + og -
+/// og én rad per delprodukt for *. Gir None for operatorer som ikke har en oppstilling
+pub fn oppstilling(tall1: &Tall, operator: &str, tall2: &Tall, svar: &Tall) -> Option<String> {
+    let mut linjer = Vec::new();
+
+    match operator {
+        "+" | "-" => {
+            // a - b er a + (-b). Har leddene likt fortegn legges tallverdiene sammen,
+            // ellers trekkes den minste tallverdien fra den største
+            let b_negativ = tall2.negativ != (operator == "-");
+
+            if tall1.negativ == b_negativ {
+                addisjon(tall1, tall2, svar, &mut linjer);
+            } else if tall1.abs() >= tall2.abs() {
+                subtraksjon(tall1, tall2, svar, &mut linjer);
+            } else {
+                subtraksjon(tall2, tall1, svar, &mut linjer);
+            }
+        }
+        "*" => multiplikasjon(tall1, tall2, svar, &mut linjer),
+        _ => return None,
+    }
+
+    let bredde = linjer
+        .iter()
+        .map(|linje| linje.innhold.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut tekst = String::new();
+
+    for linje in linjer {
+        if linje.marg.is_empty() {
+            tekst.push_str(&"-".repeat(bredde + 2));
+        } else if linje.forklaring.is_empty() {
+            tekst.push_str(&format!("{}{:>bredde$}", linje.marg, linje.innhold));
+        } else {
+            tekst.push_str(&format!(
+                "{}{:>bredde$}   {}",
+                linje.marg, linje.innhold, linje.forklaring
+            ));
+        }
+        tekst.push('\n');
+    }
+
+    if tall1.negativ || tall2.negativ || svar.negativ {
+        tekst.push_str(&format!(
+            "Regnet med tallverdiene, fortegnet til svaret følger reglene for {operator}\n"
+        ));
+    }
+
+    Some(tekst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tall(streng: &str) -> Tall {
+        streng.parse().unwrap()
+    }
+
+    fn vis(a: &str, operator: &str, b: &str) -> String {
+        let (a, b) = (tall(a), tall(b));
+        let svar = match operator {
+            "+" => a.clone() + b.clone(),
+            "-" => a.clone() - b.clone(),
+            _ => a.clone() * b.clone(),
+        };
+
+        oppstilling(&a, operator, &b, &svar).unwrap()
+    }
+
+    #[test]
+    fn addisjon_med_mente() {
+        assert_eq!(
+            vis("958", "+", "67"),
+            "  111    mente\n   958\n+   67\n------\n= 1025\n"
+        );
+        assert_eq!(
+            vis("999", "+", "1"),
+            "  111    mente\n   999\n+    1\n------\n= 1000\n"
+        );
+        // Uten mente blir det ingen menterad
+        assert_eq!(vis("12", "+", "34"), "  12\n+ 34\n----\n= 46\n");
+    }
+
+    #[test]
+    fn subtraksjon_med_lån() {
+        // Lånet går gjennom flere nuller på rad
+        assert_eq!(
+            vis("1000", "-", "1"),
+            "  111    lån, 1 lånes bort til kolonnen til høyre\n  1000\n-    1\n------\n=  999\n"
+        );
+        assert_eq!(
+            vis("5023", "-", "1748"),
+            "  111    lån, 1 lånes bort til kolonnen til høyre\n  5023\n- 1748\n------\n= 3275\n"
+        );
+        assert_eq!(vis("86", "-", "42"), "  86\n- 42\n----\n= 44\n");
+    }
+
+    #[test]
+    fn minste_tallverdi_trekkes_fra_største() {
+        assert_eq!(
+            vis("3", "-", "10"),
+            "  1    lån, 1 lånes bort til kolonnen til høyre\n  10\n-  3\n----\n= -7\n\
+             Regnet med tallverdiene, fortegnet til svaret følger reglene for -\n"
+        );
+        assert_eq!(
+            vis("-25", "+", "7"),
+            "   1    lån, 1 lånes bort til kolonnen til høyre\n   25\n-   7\n-----\n= -18\n\
+             Regnet med tallverdiene, fortegnet til svaret følger reglene for +\n"
+        );
+        // Likt fortegn gir addisjon av tallverdiene
+        assert_eq!(
+            vis("-25", "-", "7"),
+            "   1    mente\n   25\n+   7\n-----\n= -32\n\
+             Regnet med tallverdiene, fortegnet til svaret følger reglene for -\n"
+        );
+    }
+
+    #[test]
+    fn multiplikasjon_med_delprodukter() {
+        assert_eq!(
+            vis("123", "*", "45"),
+            "   123\n*   45\n------\n   615   123 * 5\n+ 492    123 * 4, flyttet 1 plass\n------\n= 5535\n"
+        );
+        // Et nullsiffer i midten gir et delprodukt som er 0
+        assert_eq!(
+            vis("507", "*", "306"),
+            "     507\n*    306\n--------\n    3042   507 * 6\n\
+             +     0    507 * 0, flyttet 1 plass\n+ 1521     507 * 3, flyttet 2 plasser\n--------\n= 155142\n"
+        );
+        assert_eq!(
+            vis("-12", "*", "34"),
+            "    12\n*   34\n------\n    48   12 * 4\n+  36    12 * 3, flyttet 1 plass\n------\n= -408\n\
+             Regnet med tallverdiene, fortegnet til svaret følger reglene for *\n"
+        );
+    }
+
+    #[test]
+    fn ett_siffer_i_multiplikatoren_gir_ingen_delprodukter() {
+        assert_eq!(vis("99", "*", "9"), "   99\n*   9\n-----\n= 891\n");
+    }
+
+    #[test]
+    fn andre_operatorer_har_ingen_oppstilling() {
+        let (a, b) = (tall("5"), tall("2"));
+        assert!(oppstilling(&a, "/", &b, &tall("2")).is_none());
+        assert!(oppstilling(&a, "^", &b, &tall("25")).is_none());
+    }
+}
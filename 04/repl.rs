@@ -0,0 +1,134 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{fjern_ord, sett_inn_ord, tremap::TreeMap, Node};
+
+fn print_kommandoer() {
+    println!("Kommandoer:");
+    println!("\tinsert ord...  Sett inn ord i treet");
+    println!("\tdelete ord...  Fjern ord fra treet");
+    println!("\tfind ord       Søk etter et ord, og vis veien søket tok");
+    println!("\tmin, max       Vis minste eller største ord i treet");
+    println!("\theight         Vis høyden til treet");
+    println!("\tprint          Tegn treet");
+    println!("\thistory        Vis tidligere kommandoer, !n kjører kommando nummer n på nytt");
+    println!("\thelp           Vis denne listen");
+    println!("\tquit           Avslutt");
+}
+
+/// Tegner treet på samme måte som resten av programmet, med fast bredde om den er angitt
+fn tegn(tre: &Node<String>, bredde: Option<usize>) {
+    match bredde {
+        Some(bredde) => tre.vis(bredde),
+        None => tre.vis_grener(),
+    }
+}
+
+/// Kjører en interaktiv løkke der brukeren kan endre og utforske treet med kommandoer fra standard input.
+/// Treet tegnes på nytt etter hver endring, og løkken avsluttes med "quit" eller når input tar slutt.
+/// Frekvensene oppdateres sammen med treet
+pub fn kjør(
+    tre: &mut Node<String>,
+    frekvenser: &mut TreeMap<String, usize>,
+    multi: bool,
+    bredde: Option<usize>,
+    normaliser: &dyn Fn(&str) -> String,
+) {
+    let mut historikk = Vec::<String>::new();
+    let mut linjer = io::stdin().lock().lines();
+
+    print_kommandoer();
+    tegn(tre, bredde);
+
+    loop {
+        print!("> ");
+        io::stdout().flush().unwrap();
+
+        let mut linje = match linjer.next() {
+            Some(Ok(linje)) => linje.trim().to_string(),
+            _ => {
+                println!();
+                return;
+            }
+        };
+
+        if linje.is_empty() {
+            continue;
+        }
+
+        // !n henter kommando nummer n fra historikken
+        if let Some(nummer) = linje.strip_prefix('!') {
+            linje = match nummer.parse::<usize>().ok().and_then(|n| historikk.get(n.wrapping_sub(1))) {
+                Some(kommando) => {
+                    println!("> {kommando}");
+                    kommando.clone()
+                }
+                None => {
+                    println!("Ingen kommando med nummer \"{nummer}\" i historikken");
+                    continue;
+                }
+            };
+        }
+
+        historikk.push(linje.clone());
+
+        let mut ord = linje.split_whitespace();
+        let kommando = ord.next().unwrap();
+        let argumenter: Vec<String> = ord.map(normaliser).collect();
+
+        match kommando {
+            "insert" => {
+                for ord in argumenter {
+                    sett_inn_ord(tre, frekvenser, ord, multi);
+                }
+                tegn(tre, bredde);
+            }
+            "delete" => {
+                for ord in argumenter {
+                    if !fjern_ord(tre, frekvenser, &ord, multi) {
+                        println!("\"{ord}\" finnes ikke i treet");
+                    }
+                }
+                tegn(tre, bredde);
+            }
+            "find" => match argumenter.first() {
+                Some(ord) => {
+                    let (sti, funnet) = tre.finn(ord);
+                    let sti: Vec<&str> = sti.iter().map(|verdi| verdi.as_str()).collect();
+
+                    println!("Sti: {}", sti.join(" -> "));
+                    if funnet {
+                        println!(
+                            "Fant \"{ord}\" etter {} sammenligninger, satt inn {} ganger",
+                            sti.len(),
+                            frekvenser.get(ord).unwrap_or(&0)
+                        );
+                    } else {
+                        println!("\"{ord}\" finnes ikke i treet");
+                    }
+                }
+                None => println!("Forventet bruk: find ord"),
+            },
+            "min" => match tre.minste() {
+                Some(verdi) => println!("{verdi}"),
+                None => println!("Treet er tomt"),
+            },
+            "max" => match tre.største() {
+                Some(verdi) => println!("{verdi}"),
+                None => println!("Treet er tomt"),
+            },
+            "height" => println!("{}", tre.høyde()),
+            "print" => tegn(tre, bredde),
+            "history" => {
+                for (nummer, kommando) in historikk.iter().enumerate() {
+                    println!("{:>4}  {kommando}", nummer + 1);
+                }
+            }
+            "help" => print_kommandoer(),
+            "quit" | "exit" => return,
+            _ => {
+                println!("Ukjent kommando \"{kommando}\"");
+                print_kommandoer();
+            }
+        }
+    }
+}
@@ -1,4 +1,14 @@
-use std::{cmp::Ordering, collections::VecDeque, fmt::Display};
+use std::{cmp::Ordering, collections::VecDeque, fmt::Display, mem, process::exit};
+
+mod fil;
+mod lagring;
+mod mengde;
+mod repl;
+mod tremap;
+mod trestrukturer;
+
+use fil::Tokenisering;
+use tremap::TreeMap;
 
 // En node er trivielt kopierbar
 #[derive(Clone)]
@@ -7,6 +17,8 @@ enum Node<T: Ord + Display + Clone> {
     /// En faktisk node med en verdi og to under-noder
     Node {
         verdi: T,
+        /// Hvor mange ganger verdien er satt inn, alltid 1 om treet brukes som en vanlig mengde
+        antall: usize,
         /// Venstre under-node, vil alltid inneholde en verdi som er mindre enn denne nodens verdi
         venstre: Box<Node<T>>,
         /// Høyre under-node, vil alltid inneholde en verdi som er større enn denne nodens verdi
@@ -26,28 +38,108 @@ impl<T: Ord + Display + Clone> Node<T> {
     pub fn ny_node(verdi: T) -> Self {
         Self::Node {
             verdi,
+            antall: 1,
             venstre: Box::new(Self::Tom),
             høyre: Box::new(Self::Tom),
         }
     }
 
     /// Setter inn en ny node med angitt verdi i treet under den angitte noden
-    pub fn sett_inn(&mut self, ny_verdi: T) -> () {
+    pub fn sett_inn(&mut self, ny_verdi: T) {
         match self {
             Self::Node {
                 verdi,
                 venstre,
                 høyre,
+                ..
             } => match ny_verdi.cmp(verdi) {
                 Ordering::Less => venstre.sett_inn(ny_verdi),
                 Ordering::Greater => høyre.sett_inn(ny_verdi),
-                Ordering::Equal => return,
+                Ordering::Equal => {}
+            },
+            Self::Tom => *self = Node::ny_node(ny_verdi),
+        }
+    }
+
+    /// Setter inn en verdi med multimengde-semantikk:
+    /// om verdien allerede finnes i treet økes antallet på noden i stedet for å forkaste verdien
+    pub fn sett_inn_telt(&mut self, ny_verdi: T) {
+        match self {
+            Self::Node {
+                verdi,
+                antall,
+                venstre,
+                høyre,
+            } => match ny_verdi.cmp(verdi) {
+                Ordering::Less => venstre.sett_inn_telt(ny_verdi),
+                Ordering::Greater => høyre.sett_inn_telt(ny_verdi),
+                Ordering::Equal => *antall += 1,
             },
             Self::Tom => *self = Node::ny_node(ny_verdi),
         }
     }
 
-    /// Returnerer en Vec<T> med verdiene fra treet ferdig sortert
+    /// Fjerner én forekomst av verdien fra treet. Noden fjernes helt når antallet når 0,
+    /// så for et tre som brukes som vanlig mengde fjernes noden med en gang.
+    /// Returnerer om verdien fantes i treet
+    pub fn fjern(&mut self, søkeverdi: &T) -> bool {
+        let retning = match self {
+            Self::Node { verdi, .. } => søkeverdi.cmp(verdi),
+            Self::Tom => return false,
+        };
+
+        match (retning, self) {
+            (Ordering::Less, Self::Node { venstre, .. }) => venstre.fjern(søkeverdi),
+            (Ordering::Greater, Self::Node { høyre, .. }) => høyre.fjern(søkeverdi),
+            (_, Self::Node { antall, .. }) if *antall > 1 => {
+                *antall -= 1;
+                true
+            }
+            (_, node) => node.fjern_denne().is_some(),
+        }
+    }
+
+    /// Tar ut verdien og antallet fra denne noden, og fyller hullet fra under-nodene
+    fn fjern_denne(&mut self) -> Option<(T, usize)> {
+        match mem::replace(self, Self::Tom) {
+            Self::Tom => None,
+            Self::Node {
+                verdi,
+                antall,
+                venstre,
+                mut høyre,
+            } => {
+                *self = match (*venstre, høyre.er_tom()) {
+                    // Med maks én under-node kan den under-noden flyttes opp direkte
+                    (Self::Tom, _) => *høyre,
+                    (venstre, true) => venstre,
+                    // Med to under-noder erstattes denne noden av den minste noden i høyre undertre
+                    (venstre, false) => {
+                        let (minste_verdi, minste_antall) = høyre.ta_ut_minste().unwrap();
+                        Self::Node {
+                            verdi: minste_verdi,
+                            antall: minste_antall,
+                            venstre: Box::new(venstre),
+                            høyre,
+                        }
+                    }
+                };
+
+                Some((verdi, antall))
+            }
+        }
+    }
+
+    /// Tar ut noden med minst verdi i treet under denne noden
+    fn ta_ut_minste(&mut self) -> Option<(T, usize)> {
+        match self {
+            Self::Node { venstre, .. } if !venstre.er_tom() => venstre.ta_ut_minste(),
+            _ => self.fjern_denne(),
+        }
+    }
+
+    /// Returnerer en Vec<T> med verdiene fra treet ferdig sortert.
+    /// Verdier som er satt inn flere ganger i en multimengde kommer med like mange ganger som de er telt
     pub fn som_liste(&self) -> Vec<T> {
         let mut buffer = Vec::<T>::new();
 
@@ -55,11 +147,12 @@ impl<T: Ord + Display + Clone> Node<T> {
             Self::Tom => {}
             Self::Node {
                 verdi,
+                antall,
                 venstre,
                 høyre,
             } => {
                 buffer.append(&mut venstre.som_liste());
-                buffer.push(verdi.clone());
+                buffer.extend(std::iter::repeat_n(verdi.clone(), *antall));
                 buffer.append(&mut høyre.som_liste());
             }
         }
@@ -67,8 +160,43 @@ impl<T: Ord + Display + Clone> Node<T> {
         buffer
     }
 
+    /// Returnerer en Vec med hver verdi i treet sammen med antallet, ferdig sortert
+    pub fn som_telt_liste(&self) -> Vec<(T, usize)> {
+        let mut buffer = Vec::<(T, usize)>::new();
+
+        if let Self::Node {
+            verdi,
+            antall,
+            venstre,
+            høyre,
+        } = self
+        {
+            buffer.append(&mut venstre.som_telt_liste());
+            buffer.push((verdi.clone(), *antall));
+            buffer.append(&mut høyre.som_telt_liste());
+        }
+
+        buffer
+    }
+
+    /// Teksten som vises for en node i utskrifter, med antallet om verdien er telt flere ganger.
+    /// En tom verdi vises som "", ellers ville noden ikke synes og ikke ta noen plass i utskriften
+    fn etikett(verdi: &T, antall: usize) -> String {
+        let mut etikett = verdi.to_string();
+
+        if etikett.is_empty() {
+            etikett.push_str("\"\"");
+        }
+
+        if antall > 1 {
+            etikett.push_str(&format!("×{antall}"));
+        }
+
+        etikett
+    }
+
     /// Genererer en riktig fjong utskrift av treet, med total bredde som angitt
-    pub fn vis(self, bredde: usize) -> () {
+    pub fn vis(&self, bredde: usize) {
         let mut kø = VecDeque::<Node<T>>::new();
         let mut node: Node<T>;
         let mut ikke_tom;
@@ -93,12 +221,13 @@ impl<T: Ord + Display + Clone> Node<T> {
                         kø.push_back(Node::Tom);
                     }
                     Node::Node {
-                        verdi,
+                        ref verdi,
+                        antall,
                         ref venstre,
                         ref høyre,
                     } => {
                         ikke_tom = true;
-                        print!("{:^print_bredde$}", verdi);
+                        print!("{:^print_bredde$}", Self::etikett(verdi, antall));
                         kø.push_back(*venstre.clone());
                         kø.push_back(*høyre.clone());
                     }
@@ -112,21 +241,572 @@ impl<T: Ord + Display + Clone> Node<T> {
             }
 
             println!();
-            print_bredde = print_bredde / 2;
+            print_bredde /= 2;
+        }
+    }
+
+    /// Skriver ut treet med grener tegnet med box-drawing-tegn.
+    /// Bredden regnes ut fra verdiene i treet, så utskriften holder seg lesbar uansett dybde og bredde på verdiene
+    pub fn vis_grener(&self) {
+        let (linjer, _, _) = self.grenlinjer();
+
+        for linje in linjer {
+            println!("{}", linje.trim_end());
+        }
+    }
+
+    /// Tegner treet under denne noden som en blokk med linjer som alle har lik bredde.
+    /// Returnerer linjene, bredden på blokken og hvilken kolonne verdien i denne noden står over
+    fn grenlinjer(&self) -> (Vec<String>, usize, usize) {
+        let (verdi, venstre, høyre) = match self {
+            Self::Tom => return (Vec::new(), 0, 0),
+            Self::Node {
+                verdi,
+                antall,
+                venstre,
+                høyre,
+            } => (Self::etikett(verdi, *antall), venstre, høyre),
+        };
+
+        let verdibredde = verdi.chars().count();
+        let (venstre_linjer, venstre_bredde, venstre_midt) = venstre.grenlinjer();
+        let (høyre_linjer, høyre_bredde, høyre_midt) = høyre.grenlinjer();
+
+        // Første linje består av verdien, med grener ut til midten av hver under-node
+        let mut første = String::new();
+
+        if venstre_bredde > 0 {
+            første.push_str(&" ".repeat(venstre_midt));
+            første.push('┌');
+            første.push_str(&"─".repeat(venstre_bredde - venstre_midt - 1));
+        }
+
+        første.push_str(&verdi);
+
+        if høyre_bredde > 0 {
+            første.push_str(&"─".repeat(høyre_midt));
+            første.push('┐');
+            første.push_str(&" ".repeat(høyre_bredde - høyre_midt - 1));
+        }
+
+        let mut linjer = vec![første];
+
+        // Under-nodene plasseres ved siden av hverandre, med luft under verdien i denne noden
+        for i in 0..usize::max(venstre_linjer.len(), høyre_linjer.len()) {
+            let venstre_del = match venstre_linjer.get(i) {
+                Some(linje) => linje.clone(),
+                None => " ".repeat(venstre_bredde),
+            };
+            let høyre_del = match høyre_linjer.get(i) {
+                Some(linje) => linje.clone(),
+                None => " ".repeat(høyre_bredde),
+            };
+
+            linjer.push(format!(
+                "{venstre_del}{}{høyre_del}",
+                " ".repeat(verdibredde)
+            ));
+        }
+
+        (
+            linjer,
+            venstre_bredde + verdibredde + høyre_bredde,
+            venstre_bredde + verdibredde / 2,
+        )
+    }
+
+    /// Genererer en beskrivelse av treet i Graphviz' DOT-format
+    pub fn til_dot(&self) -> String {
+        let mut dot = String::from("digraph søketre {\n    node [shape=circle];\n");
+        let mut teller = 0;
+
+        self.skriv_dot(&mut dot, &mut teller);
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Skriver denne noden og under-nodene til DOT-bufferen, og returnerer navnet noden fikk.
+    /// Tomme under-noder tegnes som usynlige punkter, så venstre og høyre ikke blandes sammen
+    fn skriv_dot(&self, dot: &mut String, teller: &mut usize) -> String {
+        let navn = format!("n{teller}");
+        *teller += 1;
+
+        match self {
+            Self::Tom => {
+                dot.push_str(&format!("    {navn} [shape=point, style=invis];\n"));
+            }
+            Self::Node {
+                verdi,
+                antall,
+                venstre,
+                høyre,
+            } => {
+                let etikett = Self::etikett(verdi, *antall).replace('\\', "\\\\").replace('"', "\\\"");
+                dot.push_str(&format!("    {navn} [label=\"{etikett}\"];\n"));
+
+                // Blader trenger ikke usynlige barn
+                if venstre.er_tom() && høyre.er_tom() {
+                    return navn;
+                }
+
+                for (under_node, stil) in [(venstre, "venstre"), (høyre, "høyre")] {
+                    let under_navn = under_node.skriv_dot(dot, teller);
+                    let kantstil = if under_node.er_tom() {
+                        " [style=invis]"
+                    } else {
+                        ""
+                    };
+                    dot.push_str(&format!("    {navn} -> {under_navn}{kantstil}; // {stil}\n"));
+                }
+            }
+        }
+
+        navn
+    }
+
+    /// Sjekker om en node er tom
+    pub fn er_tom(&self) -> bool {
+        matches!(self, Self::Tom)
+    }
+
+    /// Søker etter en verdi, og returnerer verdiene i nodene søket gikk innom
+    /// sammen med om verdien ble funnet
+    pub fn finn(&self, søkeverdi: &T) -> (Vec<&T>, bool) {
+        let mut sti = Vec::new();
+        let mut node = self;
+
+        while let Self::Node {
+            verdi,
+            venstre,
+            høyre,
+            ..
+        } = node
+        {
+            sti.push(verdi);
+
+            node = match søkeverdi.cmp(verdi) {
+                Ordering::Less => venstre,
+                Ordering::Greater => høyre,
+                Ordering::Equal => return (sti, true),
+            };
+        }
+
+        (sti, false)
+    }
+
+    /// Den minste verdien i treet, lengst til venstre
+    pub fn minste(&self) -> Option<&T> {
+        match self {
+            Self::Node { venstre, .. } if !venstre.er_tom() => venstre.minste(),
+            Self::Node { verdi, .. } => Some(verdi),
+            Self::Tom => None,
+        }
+    }
+
+    /// Den største verdien i treet, lengst til høyre
+    pub fn største(&self) -> Option<&T> {
+        match self {
+            Self::Node { høyre, .. } if !høyre.er_tom() => høyre.største(),
+            Self::Node { verdi, .. } => Some(verdi),
+            Self::Tom => None,
+        }
+    }
+
+    /// Antall nivåer i treet, der et tomt tre har høyde 0
+    pub fn høyde(&self) -> usize {
+        match self {
+            Self::Node { venstre, høyre, .. } => 1 + usize::max(venstre.høyde(), høyre.høyde()),
+            Self::Tom => 0,
+        }
+    }
+}
+
+fn print_hjelp(program: &str) {
+    println!("Forventet kjøring: {program} [valg] ord... [--mot ord...]");
+    println!("\t--fil fil:          Les ord fra fil i tillegg til argumentene, \"-\" leser fra standard input");
+    println!("\t--tokenisering t:   Hvordan filen deles opp i ord: mellomrom (standard), ord eller linje");
+    println!("\t--behold-store:     Ikke gjør om ordene til små bokstaver");
+    println!("\t--last fil:         Start med treet lagret i filen i stedet for et tomt tre");
+    println!("\t--lagre fil:        Lagre treet til fil, med samme form");
+    println!("\t--multi:            Tell duplikater i stedet for å forkaste dem");
+    println!("\t--fjern ord:        Fjern én forekomst av ordet etter innsetting, kan gis flere ganger");
+    println!("\t--dot fil:          Skriv treet til fil i Graphviz' DOT-format");
+    println!("\t--bredde n:         Vis treet med fast total bredde n i stedet for med grener");
+    println!("\t--repl:             Utforsk treet interaktivt etter at ordene er satt inn");
+    println!("\t--benchmark n:      Sammenlign søketreet med treap, splay-tre og B-tre på n nøkler");
+    println!("\t--orden m:          Orden på B-treet i benchmarken, minst 3, kan gis flere ganger (standard 4 og 64)");
+    println!("\t--mot ord...:       Bygg et tre til av ordene som følger, og vis mengdeoperasjoner mellom trærne");
+}
+
+/// Henter verdien som hører til et valg på kommandolinjen, og avslutter med hjelpetekst om den mangler
+fn valgverdi<'a>(argumenter: &mut impl Iterator<Item = &'a String>, program: &str) -> String {
+    match argumenter.next() {
+        Some(verdi) => verdi.clone(),
+        None => {
+            print_hjelp(program);
+            exit(1);
+        }
+    }
+}
+
+/// Setter inn et ord i treet, og teller det med i frekvensene
+fn sett_inn_ord(
+    tre: &mut Node<String>,
+    frekvenser: &mut TreeMap<String, usize>,
+    ord: String,
+    multi: bool,
+) {
+    frekvenser
+        .entry(ord.clone())
+        .and_modify(|antall| *antall += 1)
+        .or_insert(1);
+
+    if multi {
+        tre.sett_inn_telt(ord);
+    } else {
+        tre.sett_inn(ord);
+    }
+}
+
+/// Fjerner én forekomst av et ord fra treet, og holder frekvensene i takt med treet:
+/// i en multimengde telles ordet ned, ellers forsvinner det helt. Returnerer om ordet fantes i treet
+fn fjern_ord(
+    tre: &mut Node<String>,
+    frekvenser: &mut TreeMap<String, usize>,
+    ord: &String,
+    multi: bool,
+) -> bool {
+    if !tre.fjern(ord) {
+        return false;
+    }
+
+    match frekvenser.get_mut(ord) {
+        Some(antall) if multi && *antall > 1 => *antall -= 1,
+        _ => {
+            frekvenser.remove(ord);
+        }
+    }
+
+    true
+}
+
+/// Skriver ut feilmeldingen og avslutter om et resultat er en feil
+fn eller_avslutt<T>(resultat: Result<T, String>) -> T {
+    match resultat {
+        Ok(verdi) => verdi,
+        Err(melding) => {
+            println!("{melding}");
+            exit(1);
         }
     }
 }
 
 fn main() {
-    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<String> = std::env::args().collect();
+    let program = &args[0];
+
+    let mut ordliste = Vec::<String>::new();
+    let mut filer = Vec::<String>::new();
+    let mut tokenisering = Tokenisering::Mellomrom;
+    let mut små_bokstaver = true;
+    let mut last_fil: Option<String> = None;
+    let mut lagre_fil: Option<String> = None;
+    let mut dot_fil: Option<String> = None;
+    let mut bredde: Option<usize> = None;
+    let mut multi = false;
+    let mut interaktiv = false;
+    let mut fjernes = Vec::<String>::new();
+    let mut motordliste: Option<Vec<String>> = None;
+    let mut benchmark: Option<usize> = None;
+    let mut ordener = Vec::<usize>::new();
+
+    let mut argumenter = args.iter().skip(1);
+
+    while let Some(arg) = argumenter.next() {
+        match arg.as_str() {
+            "--fil" => filer.push(valgverdi(&mut argumenter, program)),
+            "--tokenisering" => {
+                tokenisering = match Tokenisering::fra_navn(&valgverdi(&mut argumenter, program)) {
+                    Some(tokenisering) => tokenisering,
+                    None => {
+                        print_hjelp(program);
+                        exit(1);
+                    }
+                }
+            }
+            "--behold-store" => små_bokstaver = false,
+            "--last" => last_fil = Some(valgverdi(&mut argumenter, program)),
+            "--lagre" => lagre_fil = Some(valgverdi(&mut argumenter, program)),
+            "--dot" => dot_fil = Some(valgverdi(&mut argumenter, program)),
+            "--multi" => multi = true,
+            "--repl" => interaktiv = true,
+            "--benchmark" => match valgverdi(&mut argumenter, program).parse() {
+                Ok(n) => benchmark = Some(n),
+                Err(_) => {
+                    print_hjelp(program);
+                    exit(1);
+                }
+            },
+            "--orden" => match valgverdi(&mut argumenter, program).parse() {
+                Ok(orden) if orden >= 3 => ordener.push(orden),
+                _ => {
+                    print_hjelp(program);
+                    exit(1);
+                }
+            },
+            "--fjern" => fjernes.push(valgverdi(&mut argumenter, program)),
+            "--bredde" => match valgverdi(&mut argumenter, program).parse() {
+                Ok(n) => bredde = Some(n),
+                Err(_) => {
+                    print_hjelp(program);
+                    exit(1);
+                }
+            },
+            "--mot" => motordliste = Some(Vec::new()),
+            _ => match motordliste.as_mut() {
+                Some(motordliste) => motordliste.push(arg.clone()),
+                None => ordliste.push(arg.clone()),
+            },
+        }
+    }
 
-    let mut tre = Node::<String>::ny_tom();
+    if let Some(n) = benchmark {
+        if ordener.is_empty() {
+            ordener = vec![4, 64];
+        }
+        trestrukturer::benchmark::kjør(n, ordener);
+        return;
+    }
 
-    for arg in args {
-        tre.sett_inn(arg.to_string().to_lowercase());
+    // REPL-en leser kommandoer fra standard input, som allerede er lest til slutten om ordene kom derfra
+    if interaktiv && filer.iter().any(|filsti| filsti == "-") {
+        println!("--fil - kan ikke kombineres med --repl, siden begge leser fra standard input");
+        exit(1);
+    }
+
+    for filsti in filer {
+        let tekst = eller_avslutt(fil::les_tekst(&filsti));
+        ordliste.append(&mut tokenisering.del_opp(&tekst));
+    }
+
+    let normaliser = |ord: &str| {
+        if små_bokstaver {
+            ord.to_lowercase()
+        } else {
+            ord.to_string()
+        }
+    };
+
+    let mut tre = match last_fil {
+        Some(filsti) => {
+            let tekst = eller_avslutt(fil::les_tekst(&filsti));
+            eller_avslutt(Node::<String>::deserialiser(&tekst))
+        }
+        None => Node::<String>::ny_tom(),
+    };
+
+    // Ord fra et lagret tre telles like mange ganger som de står i treet
+    let mut frekvenser = TreeMap::<String, usize>::new();
+    for (ord, antall) in tre.som_telt_liste() {
+        frekvenser.insert(ord, antall);
+    }
+
+    for arg in ordliste {
+        sett_inn_ord(&mut tre, &mut frekvenser, normaliser(&arg), multi);
+    }
+
+    for ord in fjernes {
+        let ord = normaliser(&ord);
+        if !fjern_ord(&mut tre, &mut frekvenser, &ord, multi) {
+            println!("\"{ord}\" finnes ikke i treet");
+        }
+    }
+
+    if interaktiv {
+        repl::kjør(&mut tre, &mut frekvenser, multi, bredde, &normaliser);
     }
 
     println!("Sortert: {:?}", tre.som_liste());
+
+    if multi {
+        println!("Antall:");
+        for (ord, antall) in tre.som_telt_liste() {
+            println!("  {ord}: {antall}");
+        }
+    } else {
+        println!("Frekvens:");
+        for (ord, antall) in frekvenser.som_liste() {
+            println!("  {ord}: {antall}");
+        }
+    }
+
+    if let Some(filsti) = lagre_fil {
+        eller_avslutt(fil::skriv_tekst(&filsti, &tre.serialiser()));
+        println!("Lagret treet til {filsti}");
+    }
+
+    if let Some(filsti) = dot_fil {
+        eller_avslutt(fil::skriv_tekst(&filsti, &tre.til_dot()));
+        println!("Skrev treet til {filsti}");
+    }
+
     println!("Treet:");
-    tre.vis(64);
+    match bredde {
+        Some(bredde) => tre.vis(bredde),
+        None => tre.vis_grener(),
+    }
+
+    if let Some(motordliste) = motordliste {
+        let mut mot = Node::<String>::ny_tom();
+
+        for arg in motordliste {
+            if multi {
+                mot.sett_inn_telt(normaliser(&arg));
+            } else {
+                mot.sett_inn(normaliser(&arg));
+            }
+        }
+
+        println!("Mot: {:?}", mot.som_liste());
+        println!("Union: {:?}", tre.union(&mot).som_liste());
+        println!("Snitt: {:?}", tre.snitt(&mot).som_liste());
+        println!("Differanse: {:?}", tre.differanse(&mot).som_liste());
+        println!(
+            "Symmetrisk differanse: {:?}",
+            tre.symmetrisk_differanse(&mot).som_liste()
+        );
+        println!("Delmengde av mot: {}", tre.er_delmengde(&mot));
+        println!("Lik mot: {}", tre == mot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tre(verdier: &[&str]) -> Node<String> {
+        let mut tre = Node::ny_tom();
+        for verdi in verdier {
+            tre.sett_inn(verdi.to_string());
+        }
+        tre
+    }
+
+    #[test]
+    fn grenlinjer_har_lik_bredde() {
+        let (linjer, bredde, _) = tre(&["m", "c", "x", "a", "e"]).grenlinjer();
+
+        assert_eq!(linjer, [" ┌─m┐", "┌c┐ x", "a e  "]);
+        assert!(linjer.iter().all(|linje| linje.chars().count() == bredde));
+    }
+
+    #[test]
+    fn tom_streng_vises_som_anførselstegn() {
+        let (linjer, _, _) = tre(&["", "b", "a"]).grenlinjer();
+
+        assert_eq!(linjer, ["\"\"─┐", "  ┌b", "  a "]);
+    }
+
+    fn telt_tre(verdier: &[&str]) -> Node<String> {
+        let mut tre = Node::ny_tom();
+        for verdi in verdier {
+            tre.sett_inn_telt(verdi.to_string());
+        }
+        tre
+    }
+
+    fn telt(par: &[(&str, usize)]) -> Vec<(String, usize)> {
+        par.iter()
+            .map(|&(verdi, antall)| (verdi.to_string(), antall))
+            .collect()
+    }
+
+    #[test]
+    fn sett_inn_telt_øker_antallet() {
+        let multimengde = telt_tre(&["b", "a", "b", "c", "b", "a"]);
+
+        assert_eq!(
+            multimengde.som_telt_liste(),
+            telt(&[("a", 2), ("b", 3), ("c", 1)])
+        );
+        assert_eq!(multimengde.som_liste(), ["a", "a", "b", "b", "b", "c"]);
+        // Gjentatte verdier gir ingen nye noder
+        assert_eq!(multimengde.høyde(), 2);
+
+        // Vanlig innsetting forkaster verdier som finnes fra før
+        assert_eq!(
+            tre(&["b", "a", "b"]).som_telt_liste(),
+            telt(&[("a", 1), ("b", 1)])
+        );
+    }
+
+    #[test]
+    fn fjern_teller_ned_før_noden_fjernes() {
+        let mut tre = telt_tre(&["b", "a", "c", "b", "b"]);
+
+        assert!(tre.fjern(&"b".to_string()));
+        assert_eq!(tre.som_telt_liste(), telt(&[("a", 1), ("b", 2), ("c", 1)]));
+        assert!(tre.fjern(&"b".to_string()));
+        assert_eq!(tre.som_telt_liste(), telt(&[("a", 1), ("b", 1), ("c", 1)]));
+        // Roten er fortsatt "b", så formen er uendret
+        assert_eq!(tre.grenlinjer().0, ["┌b┐", "a c"]);
+
+        assert!(tre.fjern(&"b".to_string()));
+        assert_eq!(tre.som_telt_liste(), telt(&[("a", 1), ("c", 1)]));
+        assert!(!tre.fjern(&"b".to_string()));
+        assert!(!tre.fjern(&"x".to_string()));
+    }
+
+    #[test]
+    fn fjern_med_to_under_noder_flytter_antallet_til_etterfølgeren() {
+        // Etterfølgeren til "d" er "e", nederst til venstre i høyre undertre, med antall 3
+        let mut tre = telt_tre(&["d", "b", "h", "a", "c", "f", "e", "g", "e", "e"]);
+
+        assert!(tre.fjern(&"d".to_string()));
+        assert_eq!(
+            tre.som_telt_liste(),
+            telt(&[
+                ("a", 1),
+                ("b", 1),
+                ("c", 1),
+                ("e", 3),
+                ("f", 1),
+                ("g", 1),
+                ("h", 1)
+            ])
+        );
+        match &tre {
+            Node::Node { verdi, antall, .. } => assert_eq!((verdi.as_str(), *antall), ("e", 3)),
+            Node::Tom => panic!("treet skulle ikke være tomt"),
+        }
+
+        // Nå er "e" roten, og må fjernes tre ganger før "f" tar over med sitt eget antall
+        tre.sett_inn_telt("f".to_string());
+        for _ in 0..3 {
+            assert!(tre.fjern(&"e".to_string()));
+        }
+        match &tre {
+            Node::Node { verdi, antall, .. } => assert_eq!((verdi.as_str(), *antall), ("f", 2)),
+            Node::Tom => panic!("treet skulle ikke være tomt"),
+        }
+        assert_eq!(tre.som_liste(), ["a", "b", "c", "f", "f", "g", "h"]);
+    }
+
+    #[test]
+    fn fjern_denne_og_ta_ut_minste_gir_verdi_og_antall() {
+        let mut tre = telt_tre(&["c", "a", "d", "b", "a", "c", "c"]);
+
+        assert_eq!(tre.ta_ut_minste(), Some(("a".to_string(), 2)));
+        assert_eq!(tre.som_telt_liste(), telt(&[("b", 1), ("c", 3), ("d", 1)]));
+
+        assert_eq!(tre.fjern_denne(), Some(("c".to_string(), 3)));
+        assert_eq!(tre.som_telt_liste(), telt(&[("b", 1), ("d", 1)]));
+
+        assert_eq!(tre.ta_ut_minste(), Some(("b".to_string(), 1)));
+        assert_eq!(tre.ta_ut_minste(), Some(("d".to_string(), 1)));
+        assert!(tre.er_tom());
+        assert_eq!(tre.ta_ut_minste(), None);
+        assert_eq!(tre.fjern_denne(), None);
+    }
 }
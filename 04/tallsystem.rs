@@ -0,0 +1,447 @@
+use std::{fmt, str::FromStr};
+
+use crate::{del_på, Tall, BASE, SIFFER_PER_BLOKK};
+
+/// Hvordan et Tall skrives ut på kommandolinjen
+#[derive(Clone, Copy)]
+pub enum Utskrift {
+    /// Vanlige desimale siffer
+    Desimal,
+    /// Desimale siffer med mellomrom mellom hver tredje, som 1 234 567
+    Tusenskille,
+    /// Alle gjeldende siffer med ett siffer før komma, som 1.234567e6
+    Vitenskapelig,
+    /// Et annet grunntall, med prefiks 0b, 0o eller 0x der det finnes
+    Grunntall(u32),
+}
+
+impl Utskrift {
+    /// Tolker navnet på en utskrift slik det skrives på kommandolinjen
+    pub fn fra_navn(navn: &str) -> Option<Self> {
+        match navn {
+            "desimal" | "10" => Some(Self::Desimal),
+            "tusenskille" => Some(Self::Tusenskille),
+            "vitenskapelig" => Some(Self::Vitenskapelig),
+            "2" | "8" | "16" | "36" => navn.parse().ok().map(Self::Grunntall),
+            _ => None,
+        }
+    }
+
+    pub fn formater(&self, tall: &Tall) -> String {
+        match self {
+            Self::Desimal => tall.to_string(),
+            Self::Tusenskille => tall.med_tusenskille(' '),
+            Self::Vitenskapelig => format!("{tall:e}"),
+            Self::Grunntall(2) => format!("{tall:#b}"),
+            Self::Grunntall(8) => format!("{tall:#o}"),
+            Self::Grunntall(16) => format!("{tall:#x}"),
+            Self::Grunntall(grunntall) => {
+                let fortegn = if tall.negativ { "-" } else { "" };
+                format!("{fortegn}{}", tall.til_grunntall(*grunntall))
+            }
+        }
+    }
+}
+
+/// Største potens av grunntallet som får plass i en u32, og eksponenten til den potensen.
+/// Så mange siffer kan tas om gangen ved konvertering mellom grunntallet og blokkene
+fn største_potens(grunntall: u32) -> (u32, usize) {
+    let mut potens = grunntall;
+    let mut eksponent = 1;
+
+    while let Some(neste) = potens.checked_mul(grunntall) {
+        potens = neste;
+        eksponent += 1;
+    }
+
+    (potens, eksponent)
+}
+
+/// Ganger blokkene med `faktor` og legger til `ledd`, på stedet
+fn gang_og_legg_til(blokker: &mut Vec<u32>, faktor: u32, ledd: u32) {
+    let mut mente = ledd as u64;
+
+    // Maks (10^9 - 1) * (2^32 - 1) + 2^32, som får plass i en u64
+    for blokk in blokker.iter_mut() {
+        let verdi = *blokk as u64 * faktor as u64 + mente;
+        *blokk = (verdi % BASE as u64) as u32;
+        mente = verdi / BASE as u64;
+    }
+
+    while mente != 0 {
+        blokker.push((mente % BASE as u64) as u32);
+        mente /= BASE as u64;
+    }
+}
+
+impl Tall {
+    /// Tolker sifre i et grunntall fra 2 til 36, der bokstavene a-z (eller A-Z) er sifrene fra 10 og opp.
+    /// Gir et positivt tall, fortegn og prefiks må være fjernet på forhånd
+    pub fn fra_grunntall(sifre: &str, grunntall: u32) -> Result<Tall, String> {
+        assert!(
+            (2..=36).contains(&grunntall),
+            "Grunntallet må være fra 2 til 36"
+        );
+
+        if sifre.is_empty() {
+            return Err("Tallet mangler sifre".to_string());
+        }
+
+        if let Some(bokstav) = sifre.chars().find(|bokstav| !bokstav.is_digit(grunntall)) {
+            return Err(format!(
+                "\"{bokstav}\" er ikke et siffer i grunntall {grunntall}"
+            ));
+        }
+
+        // Desimale sifre deles rett opp i blokker på 9 bakfra.
+        // Siste blokk (lengst til venstre i strengen) kan ha færre enn 9 siffer
+        if grunntall == 10 {
+            let blokker = sifre
+                .as_bytes()
+                .rchunks(SIFFER_PER_BLOKK)
+                .map(|blokk| {
+                    blokk
+                        .iter()
+                        .fold(0, |verdi, &siffer| verdi * 10 + (siffer - b'0') as u32)
+                })
+                .collect();
+
+            return Ok(Tall::fra_blokker(blokker));
+        }
+
+        // Andre grunntall leses med Horners metode, så mange siffer om gangen som får plass i en u32
+        let (_, per_del) = største_potens(grunntall);
+        let sifre: Vec<u32> = sifre
+            .chars()
+            .filter_map(|b| b.to_digit(grunntall))
+            .collect();
+        let mut blokker = vec![0];
+
+        for del in sifre.chunks(per_del) {
+            let faktor = grunntall.pow(del.len() as u32);
+            let verdi = del
+                .iter()
+                .fold(0, |verdi, &siffer| verdi * grunntall + siffer);
+            gang_og_legg_til(&mut blokker, faktor, verdi);
+        }
+
+        Ok(Tall::fra_blokker(blokker))
+    }
+
+    /// Skriver tallverdien i et grunntall fra 2 til 36, med små bokstaver for sifre over 9 og uten fortegn
+    pub fn til_grunntall(&self, grunntall: u32) -> String {
+        assert!(
+            (2..=36).contains(&grunntall),
+            "Grunntallet må være fra 2 til 36"
+        );
+
+        if grunntall == 10 {
+            return self.to_string().trim_start_matches('-').to_string();
+        }
+
+        // Del gjentatte ganger på den største potensen av grunntallet, hver rest blir én del av svaret
+        let (potens, per_del) = største_potens(grunntall);
+        let mut blokker = self.blokker.clone();
+        let mut deler = Vec::new();
+
+        while !(blokker.is_empty() || blokker == [0]) {
+            deler.push(del_på(&mut blokker, potens));
+        }
+
+        let skriv_del = |mut del: u32, bredde: usize| {
+            let mut sifre = Vec::with_capacity(bredde);
+            while del > 0 || sifre.len() < bredde {
+                sifre.push(char::from_digit(del % grunntall, grunntall).unwrap());
+                del /= grunntall;
+            }
+            sifre.into_iter().rev().collect::<String>()
+        };
+
+        // Øverste del skrives uten nuller foran, resten fylles ut til full bredde
+        let mut deler = deler.into_iter().rev();
+        let mut tekst = match deler.next() {
+            Some(øverste) => skriv_del(øverste, 1),
+            None => "0".to_string(),
+        };
+
+        for del in deler {
+            tekst.push_str(&skriv_del(del, per_del));
+        }
+
+        tekst
+    }
+
+    /// Skriver tallet med `skilletegn` mellom hver tredje siffer, talt bakfra
+    pub fn med_tusenskille(&self, skilletegn: char) -> String {
+        let sifre = self.til_grunntall(10);
+        let mut tekst = String::from(if self.negativ { "-" } else { "" });
+
+        for (i, siffer) in sifre.chars().enumerate() {
+            if i > 0 && (sifre.len() - i).is_multiple_of(3) {
+                tekst.push(skilletegn);
+            }
+            tekst.push(siffer);
+        }
+
+        tekst
+    }
+
+    /// Tallverdien som u128, eller None om den ikke får plass
+    fn absoluttverdi(&self) -> Option<u128> {
+        self.blokker.iter().rev().try_fold(0_u128, |verdi, &blokk| {
+            verdi.checked_mul(BASE as u128)?.checked_add(blokk as u128)
+        })
+    }
+}
+
+// Tolker et tall med valgfritt minustegn og prefiks: 0b for binært, 0o for oktalt og 0x for heksadesimalt.
+// Andre grunntall fra 2 til 36 skrives som grunntall#sifre, som 36#zz. Uten prefiks leses tallet som desimalt
+impl FromStr for Tall {
+    type Err = String;
+
+    fn from_str(streng: &str) -> Result<Self, Self::Err> {
+        let (negativ, tall) = match streng.strip_prefix('-') {
+            Some(tall) => (true, tall),
+            None => (false, streng),
+        };
+
+        let prefiks = tall.get(..2).map(str::to_ascii_lowercase);
+        let (grunntall, sifre) = match (tall.split_once('#'), prefiks.as_deref()) {
+            (Some((grunntall, sifre)), _) => match grunntall.parse() {
+                Ok(grunntall @ 2..=36) => (grunntall, sifre),
+                _ => {
+                    return Err(format!(
+                        "Ugyldig tall \"{streng}\": grunntallet må være fra 2 til 36"
+                    ))
+                }
+            },
+            (None, Some("0b")) => (2, &tall[2..]),
+            (None, Some("0o")) => (8, &tall[2..]),
+            (None, Some("0x")) => (16, &tall[2..]),
+            (None, _) => (10, tall),
+        };
+
+        if sifre.is_empty() {
+            return Err(format!("Ugyldig tall \"{streng}\""));
+        }
+
+        Tall::fra_grunntall(sifre, grunntall)
+            .map(|tall| tall.med_fortegn(negativ))
+            .map_err(|melding| format!("Ugyldig tall \"{streng}\": {melding}"))
+    }
+}
+
+impl From<u128> for Tall {
+    fn from(mut verdi: u128) -> Self {
+        let mut blokker = Vec::new();
+
+        while verdi > 0 {
+            blokker.push((verdi % BASE as u128) as u32);
+            verdi /= BASE as u128;
+        }
+
+        Tall::fra_blokker(blokker)
+    }
+}
+
+impl From<u64> for Tall {
+    fn from(verdi: u64) -> Self {
+        Tall::from(verdi as u128)
+    }
+}
+
+impl From<i128> for Tall {
+    fn from(verdi: i128) -> Self {
+        Tall::from(verdi.unsigned_abs()).med_fortegn(verdi < 0)
+    }
+}
+
+// TryFrom<Tall> for alle heltallstypene. Tallet går via u128 eller i128, og gir feil om det ikke får plass
+macro_rules! til_primitiv {
+    ($($type:ty),*) => {$(
+        impl TryFrom<Tall> for $type {
+            type Error = String;
+
+            fn try_from(tall: Tall) -> Result<Self, Self::Error> {
+                let feil = || format!("{tall} får ikke plass i en {}", stringify!($type));
+                let absoluttverdi = tall.absoluttverdi().ok_or_else(feil)?;
+
+                if tall.negativ {
+                    // i128::MIN har større tallverdi enn i128::MAX, så tallverdien trekkes fra 0 i stedet for å negeres
+                    let verdi = 0_i128.checked_sub_unsigned(absoluttverdi).ok_or_else(feil)?;
+                    <$type>::try_from(verdi).map_err(|_| feil())
+                } else {
+                    <$type>::try_from(absoluttverdi).map_err(|_| feil())
+                }
+            }
+        }
+    )*};
+}
+
+til_primitiv!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Skriver et Tall i et grunntall med fmt sin egen håndtering av fortegn, prefiks (med #) og bredde
+fn skriv_grunntall(
+    tall: &Tall,
+    f: &mut fmt::Formatter,
+    grunntall: u32,
+    prefiks: &str,
+    store_bokstaver: bool,
+) -> fmt::Result {
+    let mut sifre = tall.til_grunntall(grunntall);
+    if store_bokstaver {
+        sifre.make_ascii_uppercase();
+    }
+
+    f.pad_integral(!tall.negativ, prefiks, &sifre)
+}
+
+impl fmt::Binary for Tall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        skriv_grunntall(self, f, 2, "0b", false)
+    }
+}
+
+impl fmt::Octal for Tall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        skriv_grunntall(self, f, 8, "0o", false)
+    }
+}
+
+impl fmt::LowerHex for Tall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        skriv_grunntall(self, f, 16, "0x", false)
+    }
+}
+
+impl fmt::UpperHex for Tall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        skriv_grunntall(self, f, 16, "0x", true)
+    }
+}
+
+// Vitenskapelig notasjon som for heltallstypene i std: {:e} gir alle gjeldende siffer (1234500 blir 1.2345e6),
+// og {:.2e} runder av til angitt antall desimaler (1.23e6), med halve opp
+impl fmt::LowerExp for Tall {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut sifre: Vec<u8> = self.til_grunntall(10).into_bytes();
+        let mut eksponent = sifre.len() - 1;
+
+        match f.precision() {
+            Some(desimaler) if desimaler + 1 < sifre.len() => {
+                let rund_opp = sifre[desimaler + 1] >= b'5';
+                sifre.truncate(desimaler + 1);
+
+                if rund_opp {
+                    // Før mente bakover gjennom nitallene, blir alle sifrene 0 flyttes kommaet ett hakk
+                    match sifre.iter().rposition(|&siffer| siffer != b'9') {
+                        Some(i) => {
+                            sifre[i] += 1;
+                            sifre[i + 1..].fill(b'0');
+                        }
+                        None => {
+                            sifre.fill(b'0');
+                            sifre[0] = b'1';
+                            eksponent += 1;
+                        }
+                    }
+                }
+            }
+            Some(desimaler) => sifre.resize(desimaler + 1, b'0'),
+            None => sifre.truncate(usize::max(1, lengde_uten_nuller_bakerst(&sifre))),
+        }
+
+        let mut tekst = String::from_utf8(sifre).unwrap();
+        if tekst.len() > 1 {
+            tekst.insert(1, '.');
+        }
+
+        f.pad_integral(!self.negativ, "", &format!("{tekst}e{eksponent}"))
+    }
+}
+
+/// Antall sifre uten nuller på slutten
+fn lengde_uten_nuller_bakerst(sifre: &[u8]) -> usize {
+    sifre
+        .iter()
+        .rposition(|&siffer| siffer != b'0')
+        .map_or(0, |i| i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tall(streng: &str) -> Tall {
+        streng.parse().unwrap()
+    }
+
+    #[test]
+    fn grunntall_fram_og_tilbake() {
+        for grunntall in [2, 3, 8, 16, 36] {
+            let verdi = tall("123456789012345678901234567890");
+            assert_eq!(
+                Tall::fra_grunntall(&verdi.til_grunntall(grunntall), grunntall),
+                Ok(verdi)
+            );
+        }
+    }
+
+    #[test]
+    fn prefiks_og_grunntall_med_skilletegn() {
+        assert_eq!(tall("0b1010"), tall("10"));
+        assert_eq!(tall("0o17"), tall("15"));
+        assert_eq!(tall("-0x1F"), tall("-31"));
+        assert_eq!(tall("36#zz"), tall("1295"));
+        assert_eq!(tall("-3#210"), tall("-21"));
+        assert!("37#1".parse::<Tall>().is_err());
+        assert!("2#12".parse::<Tall>().is_err());
+        assert!("0x".parse::<Tall>().is_err());
+    }
+
+    #[test]
+    fn formatering_i_grunntall() {
+        assert_eq!(format!("{:#x}", tall("-255")), "-0xff");
+        assert_eq!(format!("{:X}", tall("255")), "FF");
+        assert_eq!(format!("{:#b}", tall("5")), "0b101");
+        assert_eq!(format!("{:#o}", tall("0")), "0o0");
+        assert_eq!(Utskrift::Grunntall(36).formater(&tall("-1295")), "-zz");
+    }
+
+    #[test]
+    fn konvertering_fra_primitive_typer() {
+        assert_eq!(
+            Tall::from(u128::MAX),
+            tall("340282366920938463463374607431768211455")
+        );
+        assert_eq!(
+            Tall::from(i128::MIN),
+            tall("-170141183460469231731687303715884105728")
+        );
+        assert_eq!(Tall::from(0_u64), tall("0"));
+    }
+
+    #[test]
+    fn konvertering_til_primitive_typer() {
+        assert_eq!(i128::try_from(Tall::from(i128::MIN)), Ok(i128::MIN));
+        assert_eq!(u8::try_from(tall("255")), Ok(255));
+        assert!(u8::try_from(tall("256")).is_err());
+        assert!(u64::try_from(tall("-1")).is_err());
+        assert_eq!(i8::try_from(tall("-128")), Ok(-128));
+        assert!(u128::try_from(Tall::from(u128::MAX) + tall("1")).is_err());
+    }
+
+    #[test]
+    fn tusenskille() {
+        assert_eq!(tall("-1234567").med_tusenskille(' '), "-1 234 567");
+        assert_eq!(tall("123").med_tusenskille(' '), "123");
+    }
+
+    #[test]
+    fn vitenskapelig_notasjon() {
+        assert_eq!(format!("{:e}", tall("1234500")), "1.2345e6");
+        assert_eq!(format!("{:.2e}", tall("-9996")), "-1.00e4");
+        assert_eq!(format!("{:.3e}", tall("12")), "1.200e1");
+        assert_eq!(format!("{:e}", tall("0")), "0e0");
+    }
+}
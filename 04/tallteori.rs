@@ -0,0 +1,272 @@
+use crate::Tall;
+
+/// Produktet av alle heltallene fra og med `fra` til (men ikke med) `til`. Halvdelene ganges sammen
+/// hver for seg, så de største multiplikasjonene blir mellom like store tall der Karatsuba gjør mest nytte
+fn produkt(fra: u64, til: u64, grense: usize) -> Tall {
+    match til.saturating_sub(fra) {
+        0 => Tall::from(1_u64),
+        1 => Tall::from(fra),
+        lengde => {
+            let midt = fra + lengde / 2;
+            produkt(fra, midt, grense).multipliser(&produkt(midt, til, grense), grense)
+        }
+    }
+}
+
+impl Tall {
+    /// Opphøyer tallet i en eksponent ved gjentatt kvadrering: a^e er (a^(e/2))^2, ganget med a én
+    /// gang til når e er odde. Det trengs dermed bare rundt 2 * log2(e) multiplikasjoner
+    pub fn potens(&self, mut eksponent: u64, grense: usize) -> Tall {
+        let mut resultat = Tall::fra_blokker(vec![1]);
+        let mut grunntall = self.clone();
+
+        // Går gjennom bitene i eksponenten fra minst signifikante, grunntallet er a^(2^i) for bit i
+        while eksponent > 0 {
+            if eksponent % 2 == 1 {
+                resultat = resultat.multipliser(&grunntall, grense);
+            }
+
+            eksponent /= 2;
+            if eksponent > 0 {
+                grunntall = grunntall.multipliser(&grunntall, grense);
+            }
+        }
+
+        resultat
+    }
+
+    /// Tallverdien til tallet, uten fortegn
+    pub fn abs(&self) -> Tall {
+        Tall::fra_blokker(self.blokker.clone())
+    }
+
+    /// Resten etter deling på `modulus`, alltid mellom 0 og |modulus| - 1.
+    /// `modulus` kan ikke være null
+    fn positiv_rest(&self, modulus: &Tall) -> Tall {
+        let (_, rest) = self.del_med_rest(modulus).expect("modulus er ikke null");
+
+        if rest.negativ {
+            rest + modulus.abs()
+        } else {
+            rest
+        }
+    }
+
+    /// Modulær potens, self^eksponent mod modulus. Resten tas etter hvert steg i kvadreringen,
+    /// så tallene aldri blir større enn modulus i andre, uansett hvor stor eksponenten er
+    pub fn modpotens(
+        &self,
+        eksponent: &Tall,
+        modulus: &Tall,
+        grense: usize,
+    ) -> Result<Tall, String> {
+        if modulus.er_null() {
+            return Err("Kan ikke regne modulo null".to_string());
+        }
+
+        if eksponent.negativ {
+            return Err("Eksponenten kan ikke være negativ".to_string());
+        }
+
+        let grunntall = self.positiv_rest(modulus);
+        let mut resultat = Tall::from(1_u64).positiv_rest(modulus);
+
+        // Går gjennom bitene i eksponenten fra mest signifikante: kvadrer for hver bit, og gang med
+        // grunntallet der biten er 1
+        for bit in eksponent.til_grunntall(2).chars() {
+            resultat = resultat
+                .multipliser(&resultat, grense)
+                .positiv_rest(modulus);
+
+            if bit == '1' {
+                resultat = resultat
+                    .multipliser(&grunntall, grense)
+                    .positiv_rest(modulus);
+            }
+        }
+
+        Ok(resultat)
+    }
+
+    /// Største felles divisor med Euklids algoritme: sfd(a, b) = sfd(b, a mod b), og sfd(a, 0) = a.
+    /// Svaret er aldri negativt, og sfd(0, 0) er 0
+    pub fn sfd(&self, rhs: &Tall) -> Tall {
+        let (mut a, mut b) = (self.abs(), rhs.abs());
+
+        while !b.er_null() {
+            let rest = a.positiv_rest(&b);
+            a = b;
+            b = rest;
+        }
+
+        a
+    }
+
+    /// Minste felles multiplum, |a * b| / sfd(a, b). Er ett av tallene null er svaret null
+    pub fn mfm(&self, rhs: &Tall, grense: usize) -> Tall {
+        if self.er_null() || rhs.er_null() {
+            return Tall::from(0_u64);
+        }
+
+        // Del før multiplikasjonen, så mellomresultatet ikke blir større enn svaret
+        let (kvotient, _) = self
+            .abs()
+            .del_med_rest(&self.sfd(rhs))
+            .expect("sfd er ikke null");
+
+        kvotient.multipliser(&rhs.abs(), grense)
+    }
+
+    /// Heltallsdelen av kvadratroten, med Newtons metode: x blir (x + n / x) / 2 til x slutter å synke.
+    /// Startverdien 10^(9 * ⌈blokker / 2⌉) er alltid minst like stor som roten, så x nærmer seg ovenfra
+    pub fn kvadratrot(&self) -> Result<Tall, String> {
+        if self.negativ {
+            return Err("Kan ikke ta kvadratroten av et negativt tall".to_string());
+        }
+
+        if self.er_null() {
+            return Ok(Tall::from(0_u64));
+        }
+
+        let mut blokker = vec![0; self.blokker.len().div_ceil(2)];
+        blokker.push(1);
+        let mut x = Tall::fra_blokker(blokker);
+        let to = Tall::from(2_u64);
+
+        loop {
+            let (n_delt_på_x, _) = self.del_med_rest(&x).expect("x er ikke null");
+            let (neste, _) = (x.clone() + n_delt_på_x)
+                .del_med_rest(&to)
+                .expect("2 er ikke null");
+
+            if neste >= x {
+                return Ok(x);
+            }
+
+            x = neste;
+        }
+    }
+
+    /// n! = 1 * 2 * ... * n
+    pub fn fakultet(n: u64, grense: usize) -> Tall {
+        produkt(1, n.saturating_add(1), grense)
+    }
+
+    /// Binomialkoeffisienten n over k, antall måter å velge k av n ting på.
+    /// Regnes ut som (n - k + 1) / 1 * (n - k + 2) / 2 * ... * n / k, der hvert mellomresultat
+    /// selv er en binomialkoeffisient og dermed et heltall, så hver divisjon går opp
+    pub fn binomial(n: &Tall, k: &Tall, grense: usize) -> Result<Tall, String> {
+        if n.negativ || k.negativ {
+            return Err("n og k kan ikke være negative i n over k".to_string());
+        }
+
+        if k > n {
+            return Ok(Tall::from(0_u64));
+        }
+
+        // n over k er lik n over n - k, så bruk den minste av dem
+        let k = Tall::min(k.clone(), n.clone() - k.clone());
+        let k = u64::try_from(k).map_err(|_| "Svaret blir for stort".to_string())?;
+        let start = n.clone() - Tall::from(k);
+
+        let mut resultat = Tall::from(1_u64);
+
+        for i in 1..=k {
+            let faktor = start.clone() + Tall::from(i);
+            let (kvotient, _) = resultat
+                .multipliser(&faktor, grense)
+                .del_med_rest(&Tall::from(i))
+                .expect("i er ikke null");
+            resultat = kvotient;
+        }
+
+        Ok(resultat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KARATSUBA_GRENSE;
+
+    fn tall(streng: &str) -> Tall {
+        streng.parse().unwrap()
+    }
+
+    #[test]
+    fn potens() {
+        assert_eq!(
+            tall("2").potens(100, KARATSUBA_GRENSE),
+            tall("1267650600228229401496703205376")
+        );
+        assert_eq!(tall("-3").potens(3, KARATSUBA_GRENSE), tall("-27"));
+        assert_eq!(tall("0").potens(0, KARATSUBA_GRENSE), tall("1"));
+    }
+
+    #[test]
+    fn modpotens() {
+        assert_eq!(
+            tall("-3").modpotens(&tall("3"), &tall("7"), KARATSUBA_GRENSE),
+            Ok(tall("1"))
+        );
+        assert_eq!(
+            tall("4").modpotens(&tall("13"), &tall("497"), KARATSUBA_GRENSE),
+            Ok(tall("445"))
+        );
+        assert_eq!(
+            tall("5").modpotens(&tall("0"), &tall("1"), KARATSUBA_GRENSE),
+            Ok(tall("0"))
+        );
+        assert!(tall("2")
+            .modpotens(&tall("3"), &tall("0"), KARATSUBA_GRENSE)
+            .is_err());
+        assert!(tall("2")
+            .modpotens(&tall("-1"), &tall("5"), KARATSUBA_GRENSE)
+            .is_err());
+    }
+
+    #[test]
+    fn sfd_og_mfm() {
+        assert_eq!(tall("-48").sfd(&tall("180")), tall("12"));
+        assert_eq!(tall("0").sfd(&tall("0")), tall("0"));
+        assert_eq!(tall("-4").mfm(&tall("6"), KARATSUBA_GRENSE), tall("12"));
+        assert_eq!(tall("0").mfm(&tall("6"), KARATSUBA_GRENSE), tall("0"));
+    }
+
+    #[test]
+    fn kvadratrot() {
+        assert_eq!(
+            tall("1000000000000000000000000").kvadratrot(),
+            Ok(tall("1000000000000"))
+        );
+        assert_eq!(
+            tall("999999999999999999999999").kvadratrot(),
+            Ok(tall("999999999999"))
+        );
+        assert_eq!(tall("1").kvadratrot(), Ok(tall("1")));
+        assert_eq!(tall("0").kvadratrot(), Ok(tall("0")));
+        assert!(tall("-4").kvadratrot().is_err());
+    }
+
+    #[test]
+    fn fakultet() {
+        assert_eq!(Tall::fakultet(0, KARATSUBA_GRENSE), tall("1"));
+        assert_eq!(
+            Tall::fakultet(25, KARATSUBA_GRENSE),
+            tall("15511210043330985984000000")
+        );
+    }
+
+    #[test]
+    fn binomial() {
+        assert_eq!(
+            Tall::binomial(&tall("60"), &tall("30"), KARATSUBA_GRENSE),
+            Ok(tall("118264581564861424"))
+        );
+        assert_eq!(
+            Tall::binomial(&tall("5"), &tall("6"), KARATSUBA_GRENSE),
+            Ok(tall("0"))
+        );
+        assert!(Tall::binomial(&tall("-5"), &tall("2"), KARATSUBA_GRENSE).is_err());
+    }
+}
@@ -0,0 +1,365 @@
+use std::{cmp::Ordering, mem};
+
+/// Nøkkel/verdi-variant av søketreet, med samme oppbygning som `Node<T>`,
+/// men hvor hver node oppbevarer en verdi i tillegg til nøkkelen den sorteres etter
+pub enum TreeMap<K: Ord, V> {
+    /// En faktisk node med nøkkel, verdi og to under-noder
+    Node {
+        nøkkel: K,
+        verdi: V,
+        /// Venstre under-node, vil alltid inneholde nøkler som er mindre enn denne nodens nøkkel
+        venstre: Box<TreeMap<K, V>>,
+        /// Høyre under-node, vil alltid inneholde nøkler som er større enn denne nodens nøkkel
+        høyre: Box<TreeMap<K, V>>,
+    },
+    /// En tom node
+    Tom,
+}
+
+impl<K: Ord, V> TreeMap<K, V> {
+    /// Oppretter et nytt, tomt tre
+    pub fn new() -> Self {
+        Self::Tom
+    }
+
+    /// Oppretter en ny node med gitt nøkkel og verdi, og tomme under-noder
+    fn ny_node(nøkkel: K, verdi: V) -> Self {
+        Self::Node {
+            nøkkel,
+            verdi,
+            venstre: Box::new(Self::Tom),
+            høyre: Box::new(Self::Tom),
+        }
+    }
+
+    /// Setter inn en verdi under angitt nøkkel.
+    /// Om nøkkelen allerede fantes i treet byttes verdien ut, og den gamle verdien returneres
+    pub fn insert(&mut self, ny_nøkkel: K, ny_verdi: V) -> Option<V> {
+        match self {
+            Self::Node {
+                nøkkel,
+                verdi,
+                venstre,
+                høyre,
+            } => match ny_nøkkel.cmp(nøkkel) {
+                Ordering::Less => venstre.insert(ny_nøkkel, ny_verdi),
+                Ordering::Greater => høyre.insert(ny_nøkkel, ny_verdi),
+                Ordering::Equal => Some(mem::replace(verdi, ny_verdi)),
+            },
+            Self::Tom => {
+                *self = Self::ny_node(ny_nøkkel, ny_verdi);
+                None
+            }
+        }
+    }
+
+    /// Henter verdien som er lagret under angitt nøkkel, om den finnes
+    pub fn get(&self, søkenøkkel: &K) -> Option<&V> {
+        match self {
+            Self::Node {
+                nøkkel,
+                verdi,
+                venstre,
+                høyre,
+            } => match søkenøkkel.cmp(nøkkel) {
+                Ordering::Less => venstre.get(søkenøkkel),
+                Ordering::Greater => høyre.get(søkenøkkel),
+                Ordering::Equal => Some(verdi),
+            },
+            Self::Tom => None,
+        }
+    }
+
+    /// Som `get`, men gir en muterbar referanse til verdien
+    pub fn get_mut(&mut self, søkenøkkel: &K) -> Option<&mut V> {
+        match self {
+            Self::Node {
+                nøkkel,
+                verdi,
+                venstre,
+                høyre,
+            } => match søkenøkkel.cmp(nøkkel) {
+                Ordering::Less => venstre.get_mut(søkenøkkel),
+                Ordering::Greater => høyre.get_mut(søkenøkkel),
+                Ordering::Equal => Some(verdi),
+            },
+            Self::Tom => None,
+        }
+    }
+
+    /// Fjerner nøkkelen fra treet, og returnerer verdien som var lagret under den
+    pub fn remove(&mut self, søkenøkkel: &K) -> Option<V> {
+        let retning = match self {
+            Self::Node { nøkkel, .. } => søkenøkkel.cmp(nøkkel),
+            Self::Tom => return None,
+        };
+
+        match (retning, self) {
+            (Ordering::Less, Self::Node { venstre, .. }) => venstre.remove(søkenøkkel),
+            (Ordering::Greater, Self::Node { høyre, .. }) => høyre.remove(søkenøkkel),
+            (_, node) => node.fjern_denne().map(|(_, verdi)| verdi),
+        }
+    }
+
+    /// Tar ut nøkkel og verdi fra denne noden, og fyller hullet fra under-nodene
+    fn fjern_denne(&mut self) -> Option<(K, V)> {
+        match mem::replace(self, Self::Tom) {
+            Self::Tom => None,
+            Self::Node {
+                nøkkel,
+                verdi,
+                venstre,
+                mut høyre,
+            } => {
+                *self = match (*venstre, høyre.er_tom()) {
+                    // Med maks én under-node kan den under-noden flyttes opp direkte
+                    (Self::Tom, _) => *høyre,
+                    (venstre, true) => venstre,
+                    // Med to under-noder erstattes denne noden av den minste noden i høyre undertre
+                    (venstre, false) => {
+                        let (minste_nøkkel, minste_verdi) = høyre.ta_ut_minste().unwrap();
+                        Self::Node {
+                            nøkkel: minste_nøkkel,
+                            verdi: minste_verdi,
+                            venstre: Box::new(venstre),
+                            høyre,
+                        }
+                    }
+                };
+
+                Some((nøkkel, verdi))
+            }
+        }
+    }
+
+    /// Tar ut noden med minst nøkkel i treet under denne noden
+    fn ta_ut_minste(&mut self) -> Option<(K, V)> {
+        match self {
+            Self::Node { venstre, .. } if !venstre.er_tom() => venstre.ta_ut_minste(),
+            _ => self.fjern_denne(),
+        }
+    }
+
+    /// Gir en `Entry` for angitt nøkkel, som kan brukes til å lese, endre eller sette inn en verdi
+    /// uten å søke gjennom treet flere ganger
+    pub fn entry(&mut self, søkenøkkel: K) -> Entry<'_, K, V> {
+        let retning = match self {
+            Self::Node { nøkkel, .. } => søkenøkkel.cmp(nøkkel),
+            Self::Tom => {
+                return Entry::Vacant(VacantEntry {
+                    node: self,
+                    nøkkel: søkenøkkel,
+                })
+            }
+        };
+
+        match (retning, self) {
+            (Ordering::Less, Self::Node { venstre, .. }) => venstre.entry(søkenøkkel),
+            (Ordering::Greater, Self::Node { høyre, .. }) => høyre.entry(søkenøkkel),
+            (_, node) => Entry::Occupied(OccupiedEntry { node }),
+        }
+    }
+
+    /// Sjekker om en node er tom
+    pub fn er_tom(&self) -> bool {
+        matches!(self, Self::Tom)
+    }
+
+    /// Returnerer en Vec med nøkkel/verdi-parene i treet, sortert etter nøkkel
+    pub fn som_liste(&self) -> Vec<(&K, &V)> {
+        let mut buffer = Vec::new();
+
+        if let Self::Node {
+            nøkkel,
+            verdi,
+            venstre,
+            høyre,
+        } = self
+        {
+            buffer.append(&mut venstre.som_liste());
+            buffer.push((nøkkel, verdi));
+            buffer.append(&mut høyre.som_liste());
+        }
+
+        buffer
+    }
+}
+
+/// En plass i treet som enten er fylt eller ledig, se `TreeMap::entry`
+pub enum Entry<'a, K: Ord, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// En node i treet som allerede har en verdi
+pub struct OccupiedEntry<'a, K: Ord, V> {
+    /// Alltid en `TreeMap::Node`
+    node: &'a mut TreeMap<K, V>,
+}
+
+/// En tom node i treet, der nøkkelen hører hjemme om den settes inn
+pub struct VacantEntry<'a, K: Ord, V> {
+    /// Alltid en `TreeMap::Tom`
+    node: &'a mut TreeMap<K, V>,
+    nøkkel: K,
+}
+
+impl<'a, K: Ord, V> Entry<'a, K, V> {
+    /// Gir verdien på denne plassen, og setter inn `standard` først om plassen er ledig
+    pub fn or_insert(self, standard: V) -> &'a mut V {
+        self.or_insert_with(|| standard)
+    }
+
+    /// Som `or_insert`, men verdien som settes inn regnes bare ut om plassen er ledig
+    pub fn or_insert_with<F: FnOnce() -> V>(self, standard: F) -> &'a mut V {
+        match self {
+            Self::Occupied(entry) => entry.into_mut(),
+            Self::Vacant(entry) => entry.insert(standard()),
+        }
+    }
+
+    /// Endrer verdien på plassen om den er fylt, og lar en ledig plass være som den er
+    pub fn and_modify<F: FnOnce(&mut V)>(self, endring: F) -> Self {
+        match self {
+            Self::Occupied(mut entry) => {
+                endring(entry.get_mut());
+                Self::Occupied(entry)
+            }
+            Self::Vacant(entry) => Self::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
+    pub fn get_mut(&mut self) -> &mut V {
+        match self.node {
+            TreeMap::Node { verdi, .. } => verdi,
+            TreeMap::Tom => unreachable!(),
+        }
+    }
+
+    /// Gjør om til en muterbar referanse til verdien, som lever like lenge som treet er lånt
+    pub fn into_mut(self) -> &'a mut V {
+        match self.node {
+            TreeMap::Node { verdi, .. } => verdi,
+            TreeMap::Tom => unreachable!(),
+        }
+    }
+}
+
+impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+    /// Setter inn verdien på den ledige plassen
+    pub fn insert(self, verdi: V) -> &'a mut V {
+        *self.node = TreeMap::ny_node(self.nøkkel, verdi);
+
+        match self.node {
+            TreeMap::Node { verdi, .. } => verdi,
+            TreeMap::Tom => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Et tre med nøklene satt inn i en rekkefølge som gir noder med null, én og to under-noder
+    fn tre() -> TreeMap<i32, &'static str> {
+        let mut tre = TreeMap::new();
+        for (nøkkel, verdi) in [
+            (50, "a"),
+            (30, "b"),
+            (70, "c"),
+            (20, "d"),
+            (40, "e"),
+            (80, "f"),
+        ] {
+            tre.insert(nøkkel, verdi);
+        }
+        tre
+    }
+
+    fn nøkler(tre: &TreeMap<i32, &'static str>) -> Vec<i32> {
+        tre.som_liste()
+            .into_iter()
+            .map(|(&nøkkel, _)| nøkkel)
+            .collect()
+    }
+
+    #[test]
+    fn insert_gir_gammel_verdi() {
+        let mut tre = tre();
+
+        assert_eq!(tre.insert(60, "g"), None);
+        assert_eq!(tre.insert(40, "h"), Some("e"));
+        assert_eq!(tre.get(&40), Some(&"h"));
+        assert_eq!(nøkler(&tre), [20, 30, 40, 50, 60, 70, 80]);
+    }
+
+    #[test]
+    fn get_og_get_mut() {
+        let mut tre = tre();
+
+        assert_eq!(tre.get(&20), Some(&"d"));
+        assert_eq!(tre.get(&25), None);
+
+        *tre.get_mut(&70).unwrap() = "z";
+        assert_eq!(tre.get(&70), Some(&"z"));
+        assert!(tre.get_mut(&25).is_none());
+    }
+
+    #[test]
+    fn remove_blad_og_noder_med_under_noder() {
+        let mut tre = tre();
+
+        // 20 er et blad, 70 har bare en høyre under-node, og 30 og 50 har to
+        assert_eq!(tre.remove(&20), Some("d"));
+        assert_eq!(tre.remove(&70), Some("c"));
+        assert_eq!(tre.remove(&50), Some("a"));
+        assert_eq!(nøkler(&tre), [30, 40, 80]);
+        assert_eq!(tre.get(&80), Some(&"f"));
+
+        assert_eq!(tre.remove(&50), None);
+        assert_eq!(tre.remove(&30), Some("b"));
+        assert_eq!(tre.remove(&40), Some("e"));
+        assert_eq!(tre.remove(&80), Some("f"));
+        assert!(tre.er_tom());
+        assert_eq!(tre.remove(&80), None);
+    }
+
+    #[test]
+    fn entry_or_insert() {
+        let mut frekvenser = TreeMap::<String, usize>::new();
+
+        for ord in ["b", "a", "b", "c", "b"] {
+            *frekvenser.entry(ord.to_string()).or_insert(0) += 1;
+        }
+
+        let liste: Vec<(&str, usize)> = frekvenser
+            .som_liste()
+            .into_iter()
+            .map(|(ord, &antall)| (ord.as_str(), antall))
+            .collect();
+        assert_eq!(liste, [("a", 1), ("b", 3), ("c", 1)]);
+    }
+
+    #[test]
+    fn entry_or_insert_with_regner_bare_ut_ved_ledig_plass() {
+        let mut tre = tre();
+
+        assert_eq!(*tre.entry(50).or_insert_with(|| unreachable!()), "a");
+        assert_eq!(*tre.entry(10).or_insert_with(|| "ny"), "ny");
+        assert_eq!(tre.get(&10), Some(&"ny"));
+    }
+
+    #[test]
+    fn entry_and_modify() {
+        let mut tre = TreeMap::<i32, usize>::new();
+
+        tre.entry(1).and_modify(|antall| *antall += 1).or_insert(1);
+        assert_eq!(tre.get(&1), Some(&1));
+
+        tre.entry(1).and_modify(|antall| *antall += 1).or_insert(1);
+        assert_eq!(tre.get(&1), Some(&2));
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::{btre::BTre, splaytre::SplayTre, treap::Treap, OrdnetMengde, Tilfeldig};
+use crate::Node;
+
+/// Antall oppslag per innsatt verdi i hver arbeidsmengde
+const OPPSLAG_PER_VERDI: usize = 10;
+
+/// Stakkstørrelse for tråden benchmarken kjører i. Det vanlige søketreet blir en lang
+/// lenke ved sortert innsetting, og både innsetting og opprydding i det går rekursivt
+const STAKKSTØRRELSE: usize = 1 << 30;
+
+/// Nøklene som settes inn, og nøklene det søkes etter etterpå
+struct Arbeidsmengde {
+    navn: &'static str,
+    innsetting: Vec<u64>,
+    oppslag: Vec<u64>,
+}
+
+impl Arbeidsmengde {
+    /// Unike nøkler i tilfeldig rekkefølge, med oppslag jevnt fordelt på nøklene
+    fn tilfeldig(n: usize, tilfeldig: &mut Tilfeldig) -> Self {
+        let innsetting = Self::stokket(n, tilfeldig);
+        let oppslag = (0..n * OPPSLAG_PER_VERDI)
+            .map(|_| innsetting[tilfeldig.under(n as u64) as usize])
+            .collect();
+
+        Self {
+            navn: "tilfeldig",
+            innsetting,
+            oppslag,
+        }
+    }
+
+    /// Nøklene settes inn i stigende rekkefølge, som er verste tilfelle for et ubalansert søketre
+    fn sortert(n: usize, tilfeldig: &mut Tilfeldig) -> Self {
+        let innsetting: Vec<u64> = (0..n as u64).collect();
+        let oppslag = (0..n * OPPSLAG_PER_VERDI)
+            .map(|_| tilfeldig.under(n as u64))
+            .collect();
+
+        Self {
+            navn: "sortert",
+            innsetting,
+            oppslag,
+        }
+    }
+
+    /// Nøkler i tilfeldig rekkefølge, men 90 % av oppslagene går til 1 % av nøklene
+    fn skjev(n: usize, tilfeldig: &mut Tilfeldig) -> Self {
+        let innsetting = Self::stokket(n, tilfeldig);
+        let populære = usize::max(n / 100, 1) as u64;
+        let oppslag = (0..n * OPPSLAG_PER_VERDI)
+            .map(|_| {
+                if tilfeldig.under(10) < 9 {
+                    innsetting[tilfeldig.under(populære) as usize]
+                } else {
+                    innsetting[tilfeldig.under(n as u64) as usize]
+                }
+            })
+            .collect();
+
+        Self {
+            navn: "skjev",
+            innsetting,
+            oppslag,
+        }
+    }
+
+    /// Tallene 0..n i tilfeldig rekkefølge (Fisher-Yates)
+    fn stokket(n: usize, tilfeldig: &mut Tilfeldig) -> Vec<u64> {
+        let mut tall: Vec<u64> = (0..n as u64).collect();
+
+        for i in (1..n).rev() {
+            tall.swap(i, tilfeldig.under(i as u64 + 1) as usize);
+        }
+
+        tall
+    }
+}
+
+/// Setter inn og søker etter nøklene i arbeidsmengden, og skriver ut en linje med resultatene
+fn mål<M: OrdnetMengde<u64>>(navn: &str, mut mengde: M, arbeidsmengde: &Arbeidsmengde) {
+    let start = Instant::now();
+    for &nøkkel in &arbeidsmengde.innsetting {
+        mengde.sett_inn(nøkkel);
+    }
+    let innsettingstid = start.elapsed();
+
+    let høyde = mengde.høyde();
+
+    let mut besøk = 0;
+    let mut ikke_funnet = 0;
+
+    let start = Instant::now();
+    for nøkkel in &arbeidsmengde.oppslag {
+        let (funnet, besøkt) = mengde.søk(nøkkel);
+        besøk += besøkt;
+        if !funnet {
+            ikke_funnet += 1;
+        }
+    }
+    let oppslagstid = start.elapsed();
+
+    println!(
+        "{navn:<16}{høyde:>8}{:>16.2}{:>14}{:>14}",
+        besøk as f64 / usize::max(arbeidsmengde.oppslag.len(), 1) as f64,
+        millisekunder(innsettingstid),
+        millisekunder(oppslagstid),
+    );
+
+    // Alle nøklene som ble satt inn skal finnes, og strukturen skal gi dem ut sortert
+    let mut fasit = arbeidsmengde.innsetting.clone();
+    fasit.sort();
+    if ikke_funnet > 0 || mengde.som_liste() != fasit {
+        println!("  Advarsel: {navn} ga feil innhold etter innsetting!");
+    }
+
+    // Fjern annenhver nøkkel, og sjekk at resten står igjen
+    for nøkkel in arbeidsmengde.innsetting.iter().step_by(2) {
+        mengde.fjern(nøkkel);
+    }
+    let mut fasit: Vec<u64> = arbeidsmengde
+        .innsetting
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .copied()
+        .collect();
+    fasit.sort();
+    if mengde.som_liste() != fasit {
+        println!("  Advarsel: {navn} ga feil innhold etter fjerning!");
+    }
+}
+
+fn millisekunder(tid: Duration) -> String {
+    format!("{:.2} ms", tid.as_secs_f64() * 1000.0)
+}
+
+/// Sammenligner høyde, besøkte noder per oppslag og kjøretid for de ulike tre-strukturene,
+/// med n nøkler i hver av arbeidsmengdene tilfeldig, sortert og skjev. B-treet måles én gang for hver orden
+pub fn kjør(n: usize, ordener: Vec<usize>) {
+    let tråd = thread::Builder::new()
+        .stack_size(STAKKSTØRRELSE)
+        .spawn(move || {
+            // Fast frø, så alle kjøringer bruker samme data
+            let mut tilfeldig = Tilfeldig::med_frø(2101);
+
+            let arbeidsmengder = [
+                Arbeidsmengde::tilfeldig(n, &mut tilfeldig),
+                Arbeidsmengde::sortert(n, &mut tilfeldig),
+                Arbeidsmengde::skjev(n, &mut tilfeldig),
+            ];
+
+            for arbeidsmengde in &arbeidsmengder {
+                println!(
+                    "Arbeidsmengde: {}, {} innsettinger, {} oppslag",
+                    arbeidsmengde.navn,
+                    arbeidsmengde.innsetting.len(),
+                    arbeidsmengde.oppslag.len()
+                );
+                println!(
+                    "{:<16}{:>8}{:>16}{:>14}{:>14}",
+                    "Struktur", "Høyde", "Besøk/oppslag", "Innsetting", "Oppslag"
+                );
+
+                mål("Søketre", Node::<u64>::ny_tom(), arbeidsmengde);
+                mål("Treap", Treap::<u64>::new(), arbeidsmengde);
+                mål("Splay-tre", SplayTre::<u64>::new(), arbeidsmengde);
+                for &orden in &ordener {
+                    let navn = format!("B-tre (m = {orden})");
+                    mål(&navn, BTre::<u64>::new(orden), arbeidsmengde);
+                }
+                println!();
+            }
+        })
+        .expect("Kunne ikke starte tråd for benchmark");
+
+    tråd.join().expect("Benchmarken krasjet");
+}
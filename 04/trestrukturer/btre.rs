@@ -0,0 +1,319 @@
+use super::OrdnetMengde;
+
+struct BNode<T> {
+    /// Sorterte nøkler, maks `orden - 1` stykker
+    nøkler: Vec<T>,
+    /// Tom for løvnoder, ellers alltid én mer enn antall nøkler.
+    /// Barn nummer i inneholder nøklene mellom nøkkel i - 1 og nøkkel i
+    barn: Vec<BNode<T>>,
+}
+
+impl<T: Ord> BNode<T> {
+    fn ny() -> Self {
+        Self {
+            nøkler: Vec::new(),
+            barn: Vec::new(),
+        }
+    }
+
+    fn er_løv(&self) -> bool {
+        self.barn.is_empty()
+    }
+
+    /// Setter inn en verdi under denne noden. Blir noden for full deles den i to,
+    /// og midterste nøkkel og høyre halvdel returneres så foreldernoden kan ta dem inn
+    fn sett_inn(&mut self, verdi: T, orden: usize) -> Option<(T, BNode<T>)> {
+        let indeks = match self.nøkler.binary_search(&verdi) {
+            Ok(_) => return None,
+            Err(indeks) => indeks,
+        };
+
+        if self.er_løv() {
+            self.nøkler.insert(indeks, verdi);
+        } else if let Some((midterste, høyre)) = self.barn[indeks].sett_inn(verdi, orden) {
+            self.nøkler.insert(indeks, midterste);
+            self.barn.insert(indeks + 1, høyre);
+        }
+
+        if self.nøkler.len() < orden {
+            return None;
+        }
+
+        // Noden har fått én nøkkel for mye, del den rundt midterste nøkkel
+        let midt = self.nøkler.len() / 2;
+        let høyre_nøkler = self.nøkler.split_off(midt + 1);
+        let midterste = self.nøkler.pop().unwrap();
+        let høyre_barn = if self.er_løv() {
+            Vec::new()
+        } else {
+            self.barn.split_off(midt + 1)
+        };
+
+        Some((
+            midterste,
+            BNode {
+                nøkler: høyre_nøkler,
+                barn: høyre_barn,
+            },
+        ))
+    }
+
+    /// Fjerner en verdi fra treet under denne noden.
+    /// Barn som ender med for få nøkler fylles opp igjen før det returneres
+    fn fjern(&mut self, verdi: &T, minste_antall: usize) -> bool {
+        match self.nøkler.binary_search(verdi) {
+            Ok(indeks) if self.er_løv() => {
+                self.nøkler.remove(indeks);
+                true
+            }
+            Ok(indeks) => {
+                // En nøkkel i en indre node erstattes av den største nøkkelen i undertreet til venstre
+                self.nøkler[indeks] = self.barn[indeks].ta_ut_største(minste_antall);
+                self.fyll_barn(indeks, minste_antall);
+                true
+            }
+            Err(_) if self.er_løv() => false,
+            Err(indeks) => {
+                let fjernet = self.barn[indeks].fjern(verdi, minste_antall);
+                self.fyll_barn(indeks, minste_antall);
+                fjernet
+            }
+        }
+    }
+
+    fn ta_ut_største(&mut self, minste_antall: usize) -> T {
+        if self.er_løv() {
+            return self.nøkler.pop().unwrap();
+        }
+
+        let indeks = self.barn.len() - 1;
+        let største = self.barn[indeks].ta_ut_største(minste_antall);
+        self.fyll_barn(indeks, minste_antall);
+        største
+    }
+
+    /// Sørger for at barn nummer `indeks` har minst `minste_antall` nøkler,
+    /// enten ved å låne en nøkkel fra et søsken eller ved å slå det sammen med et søsken
+    fn fyll_barn(&mut self, indeks: usize, minste_antall: usize) {
+        if self.barn[indeks].nøkler.len() >= minste_antall {
+            return;
+        }
+
+        if indeks > 0 && self.barn[indeks - 1].nøkler.len() > minste_antall {
+            // Lån fra venstre søsken via skillenøkkelen i denne noden
+            let (venstre, høyre) = self.barn.split_at_mut(indeks);
+            let (venstre, barn) = (&mut venstre[indeks - 1], &mut høyre[0]);
+
+            let lånt = venstre.nøkler.pop().unwrap();
+            let skille = std::mem::replace(&mut self.nøkler[indeks - 1], lånt);
+            barn.nøkler.insert(0, skille);
+
+            if let Some(barnebarn) = venstre.barn.pop() {
+                barn.barn.insert(0, barnebarn);
+            }
+        } else if indeks + 1 < self.barn.len() && self.barn[indeks + 1].nøkler.len() > minste_antall
+        {
+            // Lån fra høyre søsken via skillenøkkelen i denne noden
+            let (venstre, høyre) = self.barn.split_at_mut(indeks + 1);
+            let (barn, høyre) = (&mut venstre[indeks], &mut høyre[0]);
+
+            let lånt = høyre.nøkler.remove(0);
+            let skille = std::mem::replace(&mut self.nøkler[indeks], lånt);
+            barn.nøkler.push(skille);
+
+            if !høyre.er_løv() {
+                barn.barn.push(høyre.barn.remove(0));
+            }
+        } else if indeks > 0 {
+            self.slå_sammen(indeks - 1);
+        } else {
+            self.slå_sammen(indeks);
+        }
+    }
+
+    /// Slår sammen barn nummer `indeks` og `indeks + 1` med skillenøkkelen mellom dem
+    fn slå_sammen(&mut self, indeks: usize) {
+        let høyre = self.barn.remove(indeks + 1);
+        let skille = self.nøkler.remove(indeks);
+        let venstre = &mut self.barn[indeks];
+
+        venstre.nøkler.push(skille);
+        venstre.nøkler.extend(høyre.nøkler);
+        venstre.barn.extend(høyre.barn);
+    }
+
+    fn samle(&self, buffer: &mut Vec<T>)
+    where
+        T: Clone,
+    {
+        for (i, nøkkel) in self.nøkler.iter().enumerate() {
+            if let Some(barn) = self.barn.get(i) {
+                barn.samle(buffer);
+            }
+            buffer.push(nøkkel.clone());
+        }
+
+        if let Some(siste) = self.barn.get(self.nøkler.len()) {
+            siste.samle(buffer);
+        }
+    }
+}
+
+/// B-tre av orden m: hver node har maks m barn og m - 1 nøkler, og alle noder utenom roten
+/// har minst ⌈m/2⌉ barn. Alle løvnodene ligger på samme dybde, så høyden er alltid O(log n),
+/// og med mange nøkler per node blir det få noder å besøke per oppslag
+pub struct BTre<T> {
+    rot: BNode<T>,
+    orden: usize,
+}
+
+impl<T: Ord> BTre<T> {
+    /// Oppretter et tomt B-tre med angitt orden. Ordenen må være minst 3, noe den som kaller sjekker
+    pub fn new(orden: usize) -> Self {
+        Self {
+            rot: BNode::ny(),
+            orden,
+        }
+    }
+
+    /// Minste antall nøkler en node utenom roten kan ha, ⌈m/2⌉ - 1
+    fn minste_antall(&self) -> usize {
+        self.orden.div_ceil(2) - 1
+    }
+}
+
+impl<T: Ord + Clone> OrdnetMengde<T> for BTre<T> {
+    fn sett_inn(&mut self, verdi: T) {
+        // Deles roten får treet en ny rot, og blir ett nivå høyere
+        if let Some((midterste, høyre)) = self.rot.sett_inn(verdi, self.orden) {
+            let venstre = std::mem::replace(&mut self.rot, BNode::ny());
+            self.rot.nøkler.push(midterste);
+            self.rot.barn = vec![venstre, høyre];
+        }
+    }
+
+    fn fjern(&mut self, verdi: &T) -> bool {
+        let minste_antall = self.minste_antall();
+        let fjernet = self.rot.fjern(verdi, minste_antall);
+
+        // Står roten uten nøkler overtar det eneste barnet, og treet blir ett nivå lavere
+        if self.rot.nøkler.is_empty() && !self.rot.er_løv() {
+            self.rot = self.rot.barn.pop().unwrap();
+        }
+
+        fjernet
+    }
+
+    fn søk(&mut self, verdi: &T) -> (bool, usize) {
+        let mut node = &self.rot;
+        let mut besøk = 0;
+
+        loop {
+            besøk += 1;
+
+            match node.nøkler.binary_search(verdi) {
+                Ok(_) => return (true, besøk),
+                Err(_) if node.er_løv() => return (false, besøk),
+                Err(indeks) => node = &node.barn[indeks],
+            }
+        }
+    }
+
+    fn høyde(&self) -> usize {
+        if self.rot.nøkler.is_empty() {
+            return 0;
+        }
+
+        let mut høyde = 1;
+        let mut node = &self.rot;
+
+        while let Some(barn) = node.barn.first() {
+            høyde += 1;
+            node = barn;
+        }
+
+        høyde
+    }
+
+    fn som_liste(&self) -> Vec<T> {
+        let mut buffer = Vec::new();
+        self.rot.samle(&mut buffer);
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trestrukturer::tester;
+
+    /// Sjekker nodene under `node` på angitt dybde, og gir dybden til løvnodene
+    fn sjekk_node(node: &BNode<u64>, orden: usize, minste_antall: usize, er_rot: bool) -> usize {
+        assert!(node.nøkler.len() < orden);
+        if !er_rot {
+            assert!(node.nøkler.len() >= minste_antall);
+        }
+
+        if node.er_løv() {
+            return 1;
+        }
+
+        assert_eq!(node.barn.len(), node.nøkler.len() + 1);
+        let dybder: Vec<usize> = node
+            .barn
+            .iter()
+            .map(|barn| sjekk_node(barn, orden, minste_antall, false))
+            .collect();
+
+        // Alle løvnodene ligger på samme dybde
+        assert!(dybder.iter().all(|&dybde| dybde == dybder[0]));
+        dybder[0] + 1
+    }
+
+    fn sjekk(tre: &BTre<u64>) {
+        let dybde = sjekk_node(&tre.rot, tre.orden, tre.minste_antall(), true);
+        assert_eq!(dybde, tre.høyde().max(1));
+    }
+
+    #[test]
+    fn minste_antall_nøkler() {
+        assert_eq!(BTre::<u64>::new(3).minste_antall(), 1);
+        assert_eq!(BTre::<u64>::new(4).minste_antall(), 1);
+        assert_eq!(BTre::<u64>::new(5).minste_antall(), 2);
+        assert_eq!(BTre::<u64>::new(64).minste_antall(), 31);
+    }
+
+    #[test]
+    fn mot_btreeset() {
+        for orden in [3, 4, 5, 64] {
+            tester::mot_btreeset(BTre::new(orden), orden as u64, sjekk);
+        }
+    }
+
+    #[test]
+    fn fjern_til_tom() {
+        for orden in [3, 4, 5, 64] {
+            tester::fjern_til_tom(BTre::new(orden), 300, sjekk);
+        }
+    }
+
+    #[test]
+    fn orden_3_deler_og_slår_sammen() {
+        let mut tre = BTre::new(3);
+        for verdi in 1..=7 {
+            tre.sett_inn(verdi);
+        }
+
+        // Med maks to nøkler per node gir sju nøkler et fullt tre med tre nivåer
+        assert_eq!(tre.høyde(), 3);
+        assert_eq!(tre.rot.nøkler, [4]);
+        sjekk(&tre);
+
+        for verdi in [4, 1, 7] {
+            assert!(tre.fjern(&verdi));
+            sjekk(&tre);
+        }
+        assert_eq!(tre.som_liste(), [2, 3, 5, 6]);
+        assert!(tre.høyde() < 3);
+    }
+}
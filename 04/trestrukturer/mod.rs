@@ -0,0 +1,166 @@
+use std::{
+    fmt::Display,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::Node;
+
+pub mod benchmark;
+pub mod btre;
+pub mod splaytre;
+pub mod treap;
+
+/// Felles grensesnitt for de ulike tre-strukturene, slik at de kan byttes ut med hverandre og sammenlignes
+pub trait OrdnetMengde<T: Ord + Clone> {
+    /// Setter inn en verdi, verdier som allerede finnes forkastes
+    fn sett_inn(&mut self, verdi: T);
+
+    /// Fjerner en verdi, og returnerer om den fantes
+    fn fjern(&mut self, verdi: &T) -> bool;
+
+    /// Søker etter en verdi, og returnerer om den ble funnet sammen med hvor mange noder søket besøkte.
+    /// Tar `&mut self` siden enkelte strukturer (splay-treet) endrer form ved oppslag
+    fn søk(&mut self, verdi: &T) -> (bool, usize);
+
+    /// Antall nivåer i strukturen, der en tom struktur har høyde 0
+    fn høyde(&self) -> usize;
+
+    /// Verdiene i strukturen i sortert rekkefølge
+    fn som_liste(&self) -> Vec<T>;
+}
+
+/// Det vanlige binære søketreet
+impl<T: Ord + Display + Clone> OrdnetMengde<T> for Node<T> {
+    fn sett_inn(&mut self, verdi: T) {
+        Node::sett_inn(self, verdi)
+    }
+
+    fn fjern(&mut self, verdi: &T) -> bool {
+        Node::fjern(self, verdi)
+    }
+
+    fn søk(&mut self, verdi: &T) -> (bool, usize) {
+        let (sti, funnet) = self.finn(verdi);
+        (funnet, sti.len())
+    }
+
+    fn høyde(&self) -> usize {
+        Node::høyde(self)
+    }
+
+    fn som_liste(&self) -> Vec<T> {
+        Node::som_liste(self)
+    }
+}
+
+/// Enkel pseudo-tilfeldig tallgenerator (xorshift64*), brukt til prioriteter i treap og til å lage testdata
+pub struct Tilfeldig {
+    tilstand: u64,
+}
+
+impl Tilfeldig {
+    /// Oppretter en generator med gitt frø, samme frø gir alltid samme tallrekke
+    pub fn med_frø(frø: u64) -> Self {
+        // Tilstanden kan ikke være 0, da gir xorshift bare 0 tilbake
+        Self { tilstand: frø | 1 }
+    }
+
+    /// Oppretter en generator med frø fra klokken
+    pub fn new() -> Self {
+        let nå = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|tid| tid.as_nanos() as u64)
+            .unwrap_or(0);
+
+        Self::med_frø(nå)
+    }
+
+    pub fn neste(&mut self) -> u64 {
+        self.tilstand ^= self.tilstand >> 12;
+        self.tilstand ^= self.tilstand << 25;
+        self.tilstand ^= self.tilstand >> 27;
+        self.tilstand.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Et tall i intervallet [0, max)
+    pub fn under(&mut self, max: u64) -> u64 {
+        self.neste() % max
+    }
+}
+
+/// Felles sjekker for tre-strukturene, som går gjennom `OrdnetMengde` og sammenligner med en `BTreeSet`
+#[cfg(test)]
+pub(crate) mod tester {
+    use std::collections::BTreeSet;
+
+    use super::{OrdnetMengde, Tilfeldig};
+
+    /// Sjekker at mengden har samme innhold som fasiten, og at `sjekk` godtar formen
+    fn lik<M: OrdnetMengde<u64>>(mengde: &M, fasit: &BTreeSet<u64>, sjekk: &impl Fn(&M)) {
+        assert_eq!(
+            mengde.som_liste(),
+            fasit.iter().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(mengde.høyde() == 0, fasit.is_empty());
+        sjekk(mengde);
+    }
+
+    /// Gjør tilfeldige innsettinger, fjerninger og søk i både mengden og en `BTreeSet`, og sjekker
+    /// etter hver operasjon at de er enige. Verdiene er få nok til at mange finnes fra før
+    pub fn mot_btreeset<M: OrdnetMengde<u64>>(mut mengde: M, frø: u64, sjekk: impl Fn(&M)) {
+        let mut tilfeldig = Tilfeldig::med_frø(frø);
+        let mut fasit = BTreeSet::new();
+
+        for _ in 0..3000 {
+            let verdi = tilfeldig.under(300);
+
+            match tilfeldig.under(3) {
+                0 => {
+                    mengde.sett_inn(verdi);
+                    fasit.insert(verdi);
+                }
+                1 => assert_eq!(mengde.fjern(&verdi), fasit.remove(&verdi), "fjern {verdi}"),
+                _ => {
+                    let (funnet, besøk) = mengde.søk(&verdi);
+                    assert_eq!(funnet, fasit.contains(&verdi), "søk {verdi}");
+                    assert!(besøk <= fasit.len().max(1));
+                }
+            }
+
+            lik(&mengde, &fasit, &sjekk);
+        }
+    }
+
+    /// Setter inn verdiene i sortert rekkefølge, og fjerner dem én og én, annenhver fra hver ende,
+    /// til mengden er tom. Så må den kunne brukes på nytt
+    pub fn fjern_til_tom<M: OrdnetMengde<u64>>(mut mengde: M, antall: u64, sjekk: impl Fn(&M)) {
+        let mut fasit: BTreeSet<u64> = (0..antall).collect();
+        for verdi in 0..antall {
+            mengde.sett_inn(verdi);
+        }
+        lik(&mengde, &fasit, &sjekk);
+
+        let (mut lav, mut høy) = (0, antall);
+        while lav < høy {
+            let verdi = if fasit.len().is_multiple_of(2) {
+                lav += 1;
+                lav - 1
+            } else {
+                høy -= 1;
+                høy
+            };
+
+            assert!(mengde.fjern(&verdi));
+            assert!(!mengde.fjern(&verdi));
+            fasit.remove(&verdi);
+            lik(&mengde, &fasit, &sjekk);
+        }
+
+        assert!(!mengde.søk(&0).0);
+        assert!(!mengde.fjern(&0));
+
+        mengde.sett_inn(7);
+        assert!(mengde.søk(&7).0);
+        assert_eq!(mengde.som_liste(), [7]);
+    }
+}
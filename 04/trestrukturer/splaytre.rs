@@ -0,0 +1,273 @@
+use std::cmp::Ordering;
+
+use super::OrdnetMengde;
+
+struct SplayNode<T> {
+    verdi: T,
+    venstre: Option<Box<SplayNode<T>>>,
+    høyre: Option<Box<SplayNode<T>>>,
+}
+
+impl<T> SplayNode<T> {
+    fn ny(verdi: T) -> Box<Self> {
+        Box::new(Self {
+            verdi,
+            venstre: None,
+            høyre: None,
+        })
+    }
+}
+
+/// Binært søketre der hver node som søkes etter, settes inn eller fjernes flyttes opp til roten.
+/// Verdier som brukes ofte havner dermed nær toppen, og en serie med operasjoner koster O(log n)
+/// i snitt per operasjon selv om enkelt-operasjoner kan gå gjennom hele høyden
+pub struct SplayTre<T> {
+    rot: Option<Box<SplayNode<T>>>,
+}
+
+impl<T: Ord> SplayTre<T> {
+    pub fn new() -> Self {
+        Self { rot: None }
+    }
+
+    /// Flytter noden med angitt verdi opp til roten av treet under `node`.
+    /// Finnes ikke verdien blir siste node på søkeveien roten i stedet.
+    /// Antall besøkte noder legges til i `besøk`
+    fn splay(
+        node: Option<Box<SplayNode<T>>>,
+        verdi: &T,
+        besøk: &mut usize,
+    ) -> Option<Box<SplayNode<T>>> {
+        let mut rot = node?;
+        *besøk += 1;
+
+        match verdi.cmp(&rot.verdi) {
+            Ordering::Equal => Some(rot),
+            Ordering::Less => {
+                let mut venstre = match rot.venstre.take() {
+                    Some(venstre) => venstre,
+                    None => return Some(rot),
+                };
+                *besøk += 1;
+
+                match verdi.cmp(&venstre.verdi) {
+                    // Zig-zig: verdien ligger til venstre for venstre barn
+                    Ordering::Less => {
+                        venstre.venstre = Self::splay(venstre.venstre.take(), verdi, besøk);
+                        rot.venstre = Some(venstre);
+                        rot = Self::roter_høyre(rot);
+                    }
+                    // Zig-zag: verdien ligger til høyre for venstre barn
+                    Ordering::Greater => {
+                        venstre.høyre = Self::splay(venstre.høyre.take(), verdi, besøk);
+                        if venstre.høyre.is_some() {
+                            venstre = Self::roter_venstre(venstre);
+                        }
+                        rot.venstre = Some(venstre);
+                    }
+                    Ordering::Equal => rot.venstre = Some(venstre),
+                }
+
+                if rot.venstre.is_some() {
+                    Some(Self::roter_høyre(rot))
+                } else {
+                    Some(rot)
+                }
+            }
+            Ordering::Greater => {
+                let mut høyre = match rot.høyre.take() {
+                    Some(høyre) => høyre,
+                    None => return Some(rot),
+                };
+                *besøk += 1;
+
+                match verdi.cmp(&høyre.verdi) {
+                    // Zig-zig: verdien ligger til høyre for høyre barn
+                    Ordering::Greater => {
+                        høyre.høyre = Self::splay(høyre.høyre.take(), verdi, besøk);
+                        rot.høyre = Some(høyre);
+                        rot = Self::roter_venstre(rot);
+                    }
+                    // Zig-zag: verdien ligger til venstre for høyre barn
+                    Ordering::Less => {
+                        høyre.venstre = Self::splay(høyre.venstre.take(), verdi, besøk);
+                        if høyre.venstre.is_some() {
+                            høyre = Self::roter_høyre(høyre);
+                        }
+                        rot.høyre = Some(høyre);
+                    }
+                    Ordering::Equal => rot.høyre = Some(høyre),
+                }
+
+                if rot.høyre.is_some() {
+                    Some(Self::roter_venstre(rot))
+                } else {
+                    Some(rot)
+                }
+            }
+        }
+    }
+
+    fn roter_høyre(mut rot: Box<SplayNode<T>>) -> Box<SplayNode<T>> {
+        let mut ny_rot = rot.venstre.take().unwrap();
+        rot.venstre = ny_rot.høyre.take();
+        ny_rot.høyre = Some(rot);
+        ny_rot
+    }
+
+    fn roter_venstre(mut rot: Box<SplayNode<T>>) -> Box<SplayNode<T>> {
+        let mut ny_rot = rot.høyre.take().unwrap();
+        rot.høyre = ny_rot.venstre.take();
+        ny_rot.venstre = Some(rot);
+        ny_rot
+    }
+
+    fn høyde_under(node: &Option<Box<SplayNode<T>>>) -> usize {
+        match node {
+            Some(node) => {
+                1 + usize::max(
+                    Self::høyde_under(&node.venstre),
+                    Self::høyde_under(&node.høyre),
+                )
+            }
+            None => 0,
+        }
+    }
+
+    fn samle(node: &Option<Box<SplayNode<T>>>, buffer: &mut Vec<T>)
+    where
+        T: Clone,
+    {
+        if let Some(node) = node {
+            Self::samle(&node.venstre, buffer);
+            buffer.push(node.verdi.clone());
+            Self::samle(&node.høyre, buffer);
+        }
+    }
+}
+
+impl<T: Ord + Clone> OrdnetMengde<T> for SplayTre<T> {
+    fn sett_inn(&mut self, verdi: T) {
+        let mut besøk = 0;
+
+        // Etter splay er roten enten verdien selv, eller naboen verdien skal stå ved siden av
+        self.rot = match Self::splay(self.rot.take(), &verdi, &mut besøk) {
+            None => Some(SplayNode::ny(verdi)),
+            Some(mut rot) => match verdi.cmp(&rot.verdi) {
+                Ordering::Equal => Some(rot),
+                Ordering::Less => {
+                    let mut ny = SplayNode::ny(verdi);
+                    ny.venstre = rot.venstre.take();
+                    ny.høyre = Some(rot);
+                    Some(ny)
+                }
+                Ordering::Greater => {
+                    let mut ny = SplayNode::ny(verdi);
+                    ny.høyre = rot.høyre.take();
+                    ny.venstre = Some(rot);
+                    Some(ny)
+                }
+            },
+        };
+    }
+
+    fn fjern(&mut self, verdi: &T) -> bool {
+        let mut besøk = 0;
+
+        let mut rot = match Self::splay(self.rot.take(), verdi, &mut besøk) {
+            Some(rot) => rot,
+            None => return false,
+        };
+
+        if rot.verdi != *verdi {
+            self.rot = Some(rot);
+            return false;
+        }
+
+        // Største verdi i venstre undertre splayes opp, og får da ingen høyre under-node
+        // som høyre undertre kan hektes på
+        self.rot = match rot.venstre.take() {
+            None => rot.høyre.take(),
+            Some(venstre) => {
+                let mut ny_rot = Self::splay(Some(venstre), verdi, &mut besøk).unwrap();
+                ny_rot.høyre = rot.høyre.take();
+                Some(ny_rot)
+            }
+        };
+
+        true
+    }
+
+    fn søk(&mut self, verdi: &T) -> (bool, usize) {
+        let mut besøk = 0;
+        self.rot = Self::splay(self.rot.take(), verdi, &mut besøk);
+
+        let funnet = match &self.rot {
+            Some(rot) => rot.verdi == *verdi,
+            None => false,
+        };
+
+        (funnet, besøk)
+    }
+
+    fn høyde(&self) -> usize {
+        Self::høyde_under(&self.rot)
+    }
+
+    fn som_liste(&self) -> Vec<T> {
+        let mut buffer = Vec::new();
+        Self::samle(&self.rot, &mut buffer);
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trestrukturer::tester;
+
+    fn rotverdi(tre: &SplayTre<u64>) -> Option<u64> {
+        tre.rot.as_ref().map(|rot| rot.verdi)
+    }
+
+    #[test]
+    fn mot_btreeset() {
+        for frø in 1..4 {
+            tester::mot_btreeset(SplayTre::new(), frø, |_| {});
+        }
+    }
+
+    #[test]
+    fn fjern_til_tom() {
+        tester::fjern_til_tom(SplayTre::new(), 200, |_| {});
+    }
+
+    #[test]
+    fn søk_og_innsetting_flytter_verdien_til_roten() {
+        let mut tre = SplayTre::new();
+        for verdi in [50, 20, 80, 10, 30, 70, 90] {
+            tre.sett_inn(verdi);
+            assert_eq!(rotverdi(&tre), Some(verdi));
+        }
+
+        for verdi in [10, 90, 30, 50] {
+            assert!(tre.søk(&verdi).0);
+            assert_eq!(rotverdi(&tre), Some(verdi));
+        }
+
+        // En verdi som ikke finnes gir en nabo som rot
+        assert!(!tre.søk(&75).0);
+        assert!(matches!(rotverdi(&tre), Some(70 | 80)));
+    }
+
+    #[test]
+    fn gjentatte_søk_blir_billige() {
+        let mut tre = SplayTre::new();
+        for verdi in 0..1000 {
+            tre.sett_inn(verdi);
+        }
+
+        tre.søk(&0);
+        assert_eq!(tre.søk(&0), (true, 1));
+    }
+}
@@ -0,0 +1,227 @@
+use std::cmp::Ordering;
+
+use super::{OrdnetMengde, Tilfeldig};
+
+struct TreapNode<T> {
+    verdi: T,
+    /// Tilfeldig prioritet, en node har alltid høyere prioritet enn under-nodene sine
+    prioritet: u64,
+    venstre: Option<Box<TreapNode<T>>>,
+    høyre: Option<Box<TreapNode<T>>>,
+}
+
+/// Binært søketre der hver node også får en tilfeldig prioritet, og treet samtidig holdes som en heap
+/// etter prioritetene. Formen blir dermed som om verdiene var satt inn i tilfeldig rekkefølge,
+/// så forventet høyde er O(log n) uansett rekkefølgen verdiene faktisk kommer i
+pub struct Treap<T> {
+    rot: Option<Box<TreapNode<T>>>,
+    tilfeldig: Tilfeldig,
+}
+
+impl<T: Ord> Treap<T> {
+    pub fn new() -> Self {
+        Self {
+            rot: None,
+            tilfeldig: Tilfeldig::new(),
+        }
+    }
+
+    fn sett_inn_under(node: &mut Option<Box<TreapNode<T>>>, verdi: T, prioritet: u64) {
+        let gjeldende = match node {
+            Some(gjeldende) => gjeldende,
+            None => {
+                *node = Some(Box::new(TreapNode {
+                    verdi,
+                    prioritet,
+                    venstre: None,
+                    høyre: None,
+                }));
+                return;
+            }
+        };
+
+        // Sett inn som i et vanlig søketre, og roter den nye noden opp så lenge den har høyere prioritet
+        match verdi.cmp(&gjeldende.verdi) {
+            Ordering::Less => {
+                Self::sett_inn_under(&mut gjeldende.venstre, verdi, prioritet);
+                if gjeldende.venstre.as_ref().unwrap().prioritet > gjeldende.prioritet {
+                    Self::roter_høyre(node);
+                }
+            }
+            Ordering::Greater => {
+                Self::sett_inn_under(&mut gjeldende.høyre, verdi, prioritet);
+                if gjeldende.høyre.as_ref().unwrap().prioritet > gjeldende.prioritet {
+                    Self::roter_venstre(node);
+                }
+            }
+            Ordering::Equal => {}
+        }
+    }
+
+    fn fjern_under(node: &mut Option<Box<TreapNode<T>>>, verdi: &T) -> bool {
+        let gjeldende = match node {
+            Some(gjeldende) => gjeldende,
+            None => return false,
+        };
+
+        match verdi.cmp(&gjeldende.verdi) {
+            Ordering::Less => Self::fjern_under(&mut gjeldende.venstre, verdi),
+            Ordering::Greater => Self::fjern_under(&mut gjeldende.høyre, verdi),
+            Ordering::Equal => {
+                let (venstre, høyre) = (gjeldende.venstre.take(), gjeldende.høyre.take());
+                *node = Self::slå_sammen(venstre, høyre);
+                true
+            }
+        }
+    }
+
+    /// Slår sammen to treaps der alle verdiene i `venstre` er mindre enn verdiene i `høyre`
+    fn slå_sammen(
+        venstre: Option<Box<TreapNode<T>>>,
+        høyre: Option<Box<TreapNode<T>>>,
+    ) -> Option<Box<TreapNode<T>>> {
+        match (venstre, høyre) {
+            (None, høyre) => høyre,
+            (venstre, None) => venstre,
+            (Some(mut venstre), Some(mut høyre)) => {
+                // Noden med høyest prioritet blir roten
+                if venstre.prioritet > høyre.prioritet {
+                    venstre.høyre = Self::slå_sammen(venstre.høyre.take(), Some(høyre));
+                    Some(venstre)
+                } else {
+                    høyre.venstre = Self::slå_sammen(Some(venstre), høyre.venstre.take());
+                    Some(høyre)
+                }
+            }
+        }
+    }
+
+    fn roter_høyre(node: &mut Option<Box<TreapNode<T>>>) {
+        let mut rot = node.take().unwrap();
+        let mut ny_rot = rot.venstre.take().unwrap();
+        rot.venstre = ny_rot.høyre.take();
+        ny_rot.høyre = Some(rot);
+        *node = Some(ny_rot);
+    }
+
+    fn roter_venstre(node: &mut Option<Box<TreapNode<T>>>) {
+        let mut rot = node.take().unwrap();
+        let mut ny_rot = rot.høyre.take().unwrap();
+        rot.høyre = ny_rot.venstre.take();
+        ny_rot.venstre = Some(rot);
+        *node = Some(ny_rot);
+    }
+
+    fn høyde_under(node: &Option<Box<TreapNode<T>>>) -> usize {
+        match node {
+            Some(node) => {
+                1 + usize::max(
+                    Self::høyde_under(&node.venstre),
+                    Self::høyde_under(&node.høyre),
+                )
+            }
+            None => 0,
+        }
+    }
+
+    fn samle(node: &Option<Box<TreapNode<T>>>, buffer: &mut Vec<T>)
+    where
+        T: Clone,
+    {
+        if let Some(node) = node {
+            Self::samle(&node.venstre, buffer);
+            buffer.push(node.verdi.clone());
+            Self::samle(&node.høyre, buffer);
+        }
+    }
+}
+
+impl<T: Ord + Clone> OrdnetMengde<T> for Treap<T> {
+    fn sett_inn(&mut self, verdi: T) {
+        let prioritet = self.tilfeldig.neste();
+        Self::sett_inn_under(&mut self.rot, verdi, prioritet);
+    }
+
+    fn fjern(&mut self, verdi: &T) -> bool {
+        Self::fjern_under(&mut self.rot, verdi)
+    }
+
+    fn søk(&mut self, verdi: &T) -> (bool, usize) {
+        let mut node = &self.rot;
+        let mut besøk = 0;
+
+        while let Some(gjeldende) = node {
+            besøk += 1;
+
+            node = match verdi.cmp(&gjeldende.verdi) {
+                Ordering::Less => &gjeldende.venstre,
+                Ordering::Greater => &gjeldende.høyre,
+                Ordering::Equal => return (true, besøk),
+            };
+        }
+
+        (false, besøk)
+    }
+
+    fn høyde(&self) -> usize {
+        Self::høyde_under(&self.rot)
+    }
+
+    fn som_liste(&self) -> Vec<T> {
+        let mut buffer = Vec::new();
+        Self::samle(&self.rot, &mut buffer);
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trestrukturer::tester;
+
+    fn treap(frø: u64) -> Treap<u64> {
+        Treap {
+            rot: None,
+            tilfeldig: Tilfeldig::med_frø(frø),
+        }
+    }
+
+    /// Sjekker at ingen node har lavere prioritet enn en av under-nodene sine
+    fn heap_ordnet(node: &Option<Box<TreapNode<u64>>>) -> bool {
+        match node {
+            None => true,
+            Some(node) => [&node.venstre, &node.høyre].into_iter().all(|barn| {
+                barn.as_ref()
+                    .is_none_or(|barn| barn.prioritet <= node.prioritet)
+                    && heap_ordnet(barn)
+            }),
+        }
+    }
+
+    fn sjekk(treap: &Treap<u64>) {
+        assert!(heap_ordnet(&treap.rot));
+    }
+
+    #[test]
+    fn mot_btreeset() {
+        for frø in 1..4 {
+            tester::mot_btreeset(treap(frø), frø, sjekk);
+        }
+    }
+
+    #[test]
+    fn fjern_til_tom() {
+        tester::fjern_til_tom(treap(1), 200, sjekk);
+    }
+
+    #[test]
+    fn sortert_innsetting_gir_lav_høyde() {
+        let mut treap = treap(5);
+        for verdi in 0..1000 {
+            treap.sett_inn(verdi);
+        }
+
+        // Forventet høyde er rundt 3 ln n, et søketre uten balansering ville fått høyde 1000
+        assert!(treap.høyde() < 50);
+    }
+}
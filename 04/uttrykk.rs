@@ -0,0 +1,551 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{tallsystem::Utskrift, Tall};
+
+/// Største antall blokker et resultat av ^ kan få før utregningen avvises, omtrent 90 millioner siffer
+const MAKS_BLOKKER: u64 = 10_000_000;
+
+/// Største antall faktorer fak og binom ganger sammen før utregningen avvises
+const MAKS_FAKTORER: u64 = 100_000;
+
+/// Funksjonene som kan kalles i et uttrykk, med antall argumenter og en kort forklaring
+const FUNKSJONER: [(&str, usize, &str); 7] = [
+    ("pot", 2, "pot(a, e) er a opphøyd i e, som a ^ e"),
+    (
+        "modpot",
+        3,
+        "modpot(a, e, m) er a ^ e mod m, uten å regne ut a ^ e",
+    ),
+    ("sfd", 2, "sfd(a, b) er største felles divisor"),
+    ("mfm", 2, "mfm(a, b) er minste felles multiplum"),
+    ("rot", 1, "rot(n) er heltallsdelen av kvadratroten"),
+    ("fak", 1, "fak(n) er n!"),
+    ("binom", 2, "binom(n, k) er n over k"),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Symbol {
+    Tall(String),
+    Operator(char),
+    /// Navnet på en funksjon
+    Navn(String),
+    Komma,
+    VenstreParentes,
+    HøyreParentes,
+    Slutt,
+}
+
+/// Et symbol fra uttrykket, med kolonnen (fra 1) det startet i
+struct Token {
+    symbol: Symbol,
+    kolonne: usize,
+}
+
+/// Lager en feilmelding som viser uttrykket med en pil under kolonnen feilen gjelder
+fn feil(uttrykk: &str, kolonne: usize, melding: &str) -> String {
+    format!(
+        "{uttrykk}\n{:>kolonne$}\nFeil i kolonne {kolonne}: {melding}",
+        "^"
+    )
+}
+
+/// Deler opp uttrykket i tall, operatorer, funksjonsnavn, komma og parenteser. Mellomrom skiller symboler, men kreves ikke
+fn tokeniser(uttrykk: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut tegn = uttrykk.chars().enumerate().peekable();
+
+    while let Some((i, bokstav)) = tegn.next() {
+        let kolonne = i + 1;
+
+        let symbol = match bokstav {
+            _ if bokstav.is_whitespace() => continue,
+            // Bokstaver og # hører med til tallet, så prefiks som 0x og 36# og sifre som ff kommer med
+            '0'..='9' => {
+                let mut sifre = String::from(bokstav);
+                while let Some(&(_, siffer)) = tegn
+                    .peek()
+                    .filter(|(_, b)| b.is_ascii_alphanumeric() || *b == '#')
+                {
+                    sifre.push(siffer);
+                    tegn.next();
+                }
+                Symbol::Tall(sifre)
+            }
+            _ if bokstav.is_alphabetic() => {
+                let mut navn = String::from(bokstav);
+                while let Some(&(_, bokstav)) = tegn.peek().filter(|(_, b)| b.is_alphanumeric()) {
+                    navn.push(bokstav);
+                    tegn.next();
+                }
+                Symbol::Navn(navn)
+            }
+            '+' | '-' | '*' | '/' | '%' | '^' => Symbol::Operator(bokstav),
+            ',' => Symbol::Komma,
+            '(' => Symbol::VenstreParentes,
+            ')' => Symbol::HøyreParentes,
+            _ => {
+                return Err(feil(
+                    uttrykk,
+                    kolonne,
+                    &format!("Ukjent tegn \"{bokstav}\""),
+                ))
+            }
+        };
+
+        tokens.push(Token { symbol, kolonne });
+    }
+
+    tokens.push(Token {
+        symbol: Symbol::Slutt,
+        kolonne: uttrykk.chars().count() + 1,
+    });
+
+    Ok(tokens)
+}
+
+/// Presedens og om operatoren er høyreassosiativ. ^ binder sterkest, og 2^3^2 er 2^(3^2)
+fn presedens(operator: char) -> (u8, bool) {
+    match operator {
+        '+' | '-' => (1, false),
+        '*' | '/' | '%' => (2, false),
+        _ => (3, true),
+    }
+}
+
+/// Presedens for fortegn foran et tall, slik at -2^2 blir -(2^2) mens -2*3 blir (-2)*3
+const FORTEGN_PRESEDENS: u8 = 3;
+
+/// Rekursiv parser som regner ut uttrykket mens det leses, med presedens-klatring for de binære operatorene
+struct Parser<'a> {
+    uttrykk: &'a str,
+    tokens: Vec<Token>,
+    posisjon: usize,
+    karatsuba_grense: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn gjeldende(&self) -> &Token {
+        &self.tokens[self.posisjon]
+    }
+
+    fn feil(&self, kolonne: usize, melding: &str) -> String {
+        feil(self.uttrykk, kolonne, melding)
+    }
+
+    /// Leser et uttrykk der alle binære operatorer har minst angitt presedens
+    fn uttrykk(&mut self, minste_presedens: u8) -> Result<Tall, String> {
+        let mut venstre = self.operand()?;
+
+        while let Symbol::Operator(operator) = self.gjeldende().symbol {
+            let (presedens, høyreassosiativ) = presedens(operator);
+            if presedens < minste_presedens {
+                break;
+            }
+
+            let kolonne = self.gjeldende().kolonne;
+            self.posisjon += 1;
+
+            // Høyreassosiative operatorer lar høyresiden ta med seg operatorer med samme presedens
+            let neste_presedens = if høyreassosiativ {
+                presedens
+            } else {
+                presedens + 1
+            };
+            let høyre = self.uttrykk(neste_presedens)?;
+
+            venstre = self.regn_ut(venstre, operator, høyre, kolonne)?;
+        }
+
+        Ok(venstre)
+    }
+
+    /// Leser et tall, et uttrykk i parentes, et funksjonskall eller et tall med fortegn foran
+    fn operand(&mut self) -> Result<Tall, String> {
+        let token = self.gjeldende();
+        let kolonne = token.kolonne;
+
+        match token.symbol.clone() {
+            Symbol::Tall(sifre) => {
+                self.posisjon += 1;
+                sifre
+                    .parse()
+                    .map_err(|melding: String| self.feil(kolonne, &melding))
+            }
+            Symbol::Operator(fortegn @ ('-' | '+')) => {
+                self.posisjon += 1;
+                let operand = self.uttrykk(FORTEGN_PRESEDENS)?;
+                Ok(if fortegn == '-' { -operand } else { operand })
+            }
+            Symbol::VenstreParentes => {
+                self.posisjon += 1;
+                let verdi = self.uttrykk(0)?;
+
+                if self.gjeldende().symbol != Symbol::HøyreParentes {
+                    return Err(self.feil(
+                        self.gjeldende().kolonne,
+                        &format!("Forventet \")\" til parentesen i kolonne {kolonne}"),
+                    ));
+                }
+
+                self.posisjon += 1;
+                Ok(verdi)
+            }
+            Symbol::Navn(navn) => {
+                self.posisjon += 1;
+                let argumenter = self.argumenter()?;
+                self.kall(&navn, argumenter, kolonne)
+            }
+            Symbol::Slutt => Err(self.feil(kolonne, "Uttrykket slutter for tidlig")),
+            _ => Err(self.feil(kolonne, "Forventet et tall eller \"(\"")),
+        }
+    }
+
+    /// Leser argumentene til et funksjonskall, en liste med uttrykk skilt med komma inne i parenteser
+    fn argumenter(&mut self) -> Result<Vec<(Tall, usize)>, String> {
+        let start = self.gjeldende().kolonne;
+
+        if self.gjeldende().symbol != Symbol::VenstreParentes {
+            return Err(self.feil(start, "Forventet \"(\" etter funksjonsnavnet"));
+        }
+        self.posisjon += 1;
+
+        let mut argumenter = Vec::new();
+
+        loop {
+            let kolonne = self.gjeldende().kolonne;
+            argumenter.push((self.uttrykk(0)?, kolonne));
+
+            match self.gjeldende().symbol {
+                Symbol::Komma => self.posisjon += 1,
+                Symbol::HøyreParentes => {
+                    self.posisjon += 1;
+                    return Ok(argumenter);
+                }
+                _ => {
+                    return Err(self.feil(
+                        self.gjeldende().kolonne,
+                        &format!("Forventet \",\" eller \")\" til parentesen i kolonne {start}"),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Gjør om et argument til et ikke-negativt heltall som er lite nok til å telle med
+    fn antall(&self, (verdi, kolonne): &(Tall, usize), maks: u64) -> Result<u64, String> {
+        if verdi.negativ {
+            return Err(self.feil(*kolonne, "Argumentet kan ikke være negativt"));
+        }
+
+        match u64::try_from(verdi.clone()) {
+            Ok(antall) if antall <= maks => Ok(antall),
+            _ => Err(self.feil(*kolonne, "Svaret blir for stort")),
+        }
+    }
+
+    fn kall(
+        &self,
+        navn: &str,
+        argumenter: Vec<(Tall, usize)>,
+        kolonne: usize,
+    ) -> Result<Tall, String> {
+        let antall_argumenter = match FUNKSJONER.iter().find(|(funksjon, _, _)| *funksjon == navn) {
+            Some((_, antall, _)) => *antall,
+            None => return Err(self.feil(kolonne, &format!("Ukjent funksjon \"{navn}\""))),
+        };
+
+        if argumenter.len() != antall_argumenter {
+            return Err(self.feil(
+                kolonne,
+                &format!(
+                    "{navn} tar {antall_argumenter} argument(er), men fikk {}",
+                    argumenter.len()
+                ),
+            ));
+        }
+
+        let grense = self.karatsuba_grense;
+
+        match navn {
+            "pot" => {
+                let (eksponent, eksponent_kolonne) = argumenter[1].clone();
+                self.regn_ut(argumenter[0].0.clone(), '^', eksponent, eksponent_kolonne)
+            }
+            "modpot" => {
+                // Feilen gjelder modulus om den er null, ellers eksponenten
+                let feilaktig = if argumenter[2].0.er_null() {
+                    &argumenter[2]
+                } else {
+                    &argumenter[1]
+                };
+
+                argumenter[0]
+                    .0
+                    .modpotens(&argumenter[1].0, &argumenter[2].0, grense)
+                    .map_err(self.feil_ved(feilaktig))
+            }
+            "sfd" => Ok(argumenter[0].0.sfd(&argumenter[1].0)),
+            "mfm" => Ok(argumenter[0].0.mfm(&argumenter[1].0, grense)),
+            "rot" => argumenter[0]
+                .0
+                .kvadratrot()
+                .map_err(self.feil_ved(&argumenter[0])),
+            "fak" => Ok(Tall::fakultet(
+                self.antall(&argumenter[0], MAKS_FAKTORER)?,
+                grense,
+            )),
+            _ => {
+                // n over k er lik n over n - k, så det er den minste av dem som avgjør antall faktorer
+                let (n, k) = (&argumenter[0].0, &argumenter[1].0);
+                let minste = Tall::min(k.clone(), n.clone() - k.clone());
+
+                if !minste.negativ && minste > Tall::from(MAKS_FAKTORER) {
+                    return Err(self.feil(argumenter[1].1, "Svaret blir for stort"));
+                }
+
+                // Feilen gjelder n om den er negativ, ellers k
+                let feilaktig = if n.negativ {
+                    &argumenter[0]
+                } else {
+                    &argumenter[1]
+                };
+
+                Tall::binomial(n, k, grense).map_err(self.feil_ved(feilaktig))
+            }
+        }
+    }
+
+    /// Gjør om en feilmelding fra en utregning til en feil som peker på argumentet den gjelder
+    fn feil_ved<'b>(
+        &'b self,
+        (_, kolonne): &'b (Tall, usize),
+    ) -> impl FnOnce(String) -> String + 'b {
+        move |melding| self.feil(*kolonne, &melding)
+    }
+
+    fn regn_ut(
+        &self,
+        venstre: Tall,
+        operator: char,
+        høyre: Tall,
+        kolonne: usize,
+    ) -> Result<Tall, String> {
+        match operator {
+            '+' => Ok(venstre + høyre),
+            '-' => Ok(venstre - høyre),
+            '*' => Ok(venstre.multipliser(&høyre, self.karatsuba_grense)),
+            '/' => venstre
+                .del_med_rest(&høyre)
+                .map(|(kvotient, _)| kvotient)
+                .map_err(|melding| self.feil(kolonne, &melding)),
+            '%' => venstre
+                .del_med_rest(&høyre)
+                .map(|(_, rest)| rest)
+                .map_err(|melding| self.feil(kolonne, &melding)),
+            _ => {
+                if høyre.negativ {
+                    return Err(self.feil(kolonne, "Eksponenten kan ikke være negativ"));
+                }
+
+                // Grunntall 0 og ±1 gir små svar uansett eksponent, ellers vokser svaret med eksponenten
+                let liten = venstre.blokker.len() == 1 && venstre.blokker[0] <= 1;
+                let eksponent = match u64::try_from(høyre).ok() {
+                    Some(eksponent)
+                        if liten
+                            || eksponent.saturating_mul(venstre.blokker.len() as u64)
+                                <= MAKS_BLOKKER =>
+                    {
+                        eksponent
+                    }
+                    _ => return Err(self.feil(kolonne, "Svaret blir for stort")),
+                };
+
+                Ok(venstre.potens(eksponent, self.karatsuba_grense))
+            }
+        }
+    }
+}
+
+/// Regner ut et uttrykk med + - * / % ^ og parenteser. Feil gis som en melding som peker på kolonnen de gjelder
+pub fn regn_ut(uttrykk: &str, karatsuba_grense: usize) -> Result<Tall, String> {
+    let mut parser = Parser {
+        uttrykk,
+        tokens: tokeniser(uttrykk)?,
+        posisjon: 0,
+        karatsuba_grense,
+    };
+
+    let verdi = parser.uttrykk(0)?;
+
+    match parser.gjeldende() {
+        Token {
+            symbol: Symbol::Slutt,
+            ..
+        } => Ok(verdi),
+        Token {
+            symbol: Symbol::HøyreParentes,
+            kolonne,
+        } => Err(parser.feil(*kolonne, "\")\" uten \"(\" foran")),
+        Token { kolonne, .. } => Err(parser.feil(*kolonne, "Forventet en operator")),
+    }
+}
+
+/// Liste over funksjonene som kan brukes i et uttrykk, til hjelpetekster
+pub fn funksjonsliste() -> String {
+    let mut liste = String::from("Funksjoner:");
+
+    for (_, _, forklaring) in FUNKSJONER {
+        liste.push_str(&format!("\n\t{forklaring}"));
+    }
+
+    liste
+}
+
+/// Leser uttrykk fra standard input og skriver ut svaret på hvert av dem,
+/// til brukeren skriver "quit" eller input tar slutt
+pub fn repl(karatsuba_grense: usize, utskrift: Utskrift) {
+    let mut linjer = io::stdin().lock().lines();
+
+    println!("Skriv inn et uttrykk med + - * / % ^ og parenteser, eller \"quit\" for å avslutte");
+    println!("{}", funksjonsliste());
+
+    loop {
+        print!("> ");
+        io::stdout().flush().unwrap();
+
+        let linje = match linjer.next() {
+            Some(Ok(linje)) => linje.trim().to_string(),
+            _ => {
+                println!();
+                return;
+            }
+        };
+
+        match linje.as_str() {
+            "" => continue,
+            "quit" | "exit" => return,
+            _ => {}
+        }
+
+        match regn_ut(&linje, karatsuba_grense) {
+            Ok(svar) => println!("{}", utskrift.formater(&svar)),
+            Err(melding) => println!("{melding}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KARATSUBA_GRENSE;
+
+    fn tall(streng: &str) -> Tall {
+        streng.parse().unwrap()
+    }
+
+    fn regn(uttrykk: &str) -> Tall {
+        regn_ut(uttrykk, KARATSUBA_GRENSE).unwrap()
+    }
+
+    /// Kolonnen feilmeldingen for uttrykket peker på
+    fn feilkolonne(uttrykk: &str) -> usize {
+        let melding = regn_ut(uttrykk, KARATSUBA_GRENSE).unwrap_err();
+        let (_, resten) = melding.split_once("Feil i kolonne ").unwrap();
+        let (kolonne, _) = resten.split_once(':').unwrap();
+
+        // Pilen på linjen over skal stå i samme kolonne
+        let pil = melding.lines().nth(1).unwrap();
+        assert_eq!(
+            pil.chars().count(),
+            kolonne.parse::<usize>().unwrap(),
+            "{melding}"
+        );
+
+        kolonne.parse().unwrap()
+    }
+
+    #[test]
+    fn presedens() {
+        assert_eq!(regn("2 + 3 * 4"), tall("14"));
+        assert_eq!(regn("(2 + 3) * 4"), tall("20"));
+        assert_eq!(regn("20 - 6 / 3 - 1"), tall("17"));
+        assert_eq!(regn("17 % 5 * 2"), tall("4"));
+        assert_eq!(regn("2 * 3 ^ 2"), tall("18"));
+        assert_eq!(regn("((7))"), tall("7"));
+    }
+
+    #[test]
+    fn potens_er_høyreassosiativ() {
+        assert_eq!(regn("2 ^ 3 ^ 2"), tall("512"));
+        assert_eq!(regn("(2 ^ 3) ^ 2"), tall("64"));
+    }
+
+    #[test]
+    fn fortegn() {
+        assert_eq!(regn("-2^2"), tall("-4"));
+        assert_eq!(regn("(-2)^2"), tall("4"));
+        assert_eq!(regn("-2*3"), tall("-6"));
+        assert_eq!(regn("3 - -2"), tall("5"));
+        assert_eq!(regn("+5"), tall("5"));
+    }
+
+    #[test]
+    fn funksjoner() {
+        assert_eq!(regn("pot(2, 10)"), tall("1024"));
+        assert_eq!(regn("modpot(4, 13, 497)"), tall("445"));
+        assert_eq!(regn("sfd(48, 180)"), tall("12"));
+        assert_eq!(regn("mfm(4, 6)"), tall("12"));
+        assert_eq!(regn("rot(1000001)"), tall("1000"));
+        assert_eq!(regn("fak(20)"), tall("2432902008176640000"));
+        assert_eq!(regn("binom(50, 25)"), tall("126410606437752"));
+        assert_eq!(regn("binom(3, 5)"), tall("0"));
+        assert_eq!(regn("1 + fak(3) * pot(2, 2)"), tall("25"));
+    }
+
+    #[test]
+    fn grenser() {
+        assert_eq!(feilkolonne("2 ^ 10000001"), 3);
+        assert_eq!(feilkolonne("pot(10, 100000000000)"), 9);
+        assert_eq!(regn("1 ^ 100000000000"), tall("1"));
+        assert_eq!(regn("0 ^ 100000000000"), tall("0"));
+
+        assert_eq!(feilkolonne("fak(100001)"), 5);
+        assert_eq!(regn("fak(0)"), tall("1"));
+        assert_eq!(feilkolonne("binom(1000000, 200000)"), 16);
+        assert_eq!(regn("binom(1000000, 999999)"), tall("1000000"));
+    }
+
+    #[test]
+    fn feilmelding_peker_på_kolonnen() {
+        assert_eq!(
+            regn_ut("1 + * 2", KARATSUBA_GRENSE),
+            Err("1 + * 2\n    ^\nFeil i kolonne 5: Forventet et tall eller \"(\"".to_string())
+        );
+        assert_eq!(feilkolonne("1 $ 2"), 3);
+        assert_eq!(feilkolonne("(1 + 2"), 7);
+        assert_eq!(feilkolonne("1 + 2)"), 6);
+        assert_eq!(feilkolonne("1 2"), 3);
+        assert_eq!(feilkolonne("1 +"), 4);
+        assert_eq!(feilkolonne("7 / (3 - 3)"), 3);
+        assert_eq!(feilkolonne("7 % 0"), 3);
+        assert_eq!(feilkolonne("2 ^ -1"), 3);
+    }
+
+    #[test]
+    fn feil_i_funksjonskall() {
+        assert_eq!(feilkolonne("1 + ukjent(2)"), 5);
+        assert_eq!(feilkolonne("sfd(2)"), 1);
+        assert_eq!(feilkolonne("rot 4"), 5);
+        assert_eq!(feilkolonne("sfd(2; 3)"), 6);
+    }
+
+    #[test]
+    fn feil_i_argument_peker_på_argumentet() {
+        assert_eq!(feilkolonne("binom(5,-1)"), 9);
+        assert_eq!(feilkolonne("binom(-5, 2)"), 7);
+        assert_eq!(feilkolonne("modpot(2,10,0)"), 13);
+        assert_eq!(feilkolonne("modpot(2, -1, 5)"), 11);
+        assert_eq!(feilkolonne("rot(-4)"), 5);
+        assert_eq!(feilkolonne("fak(-1)"), 5);
+    }
+}
@@ -0,0 +1,251 @@
+use std::mem;
+
+use crate::hashfunksjoner::bland;
+
+/// Gjøkhashing (cuckoo hashing): to tabeller med hver sin hashfunksjon, der hver nøkkel står på
+/// plassen sin i enten den ene eller den andre. Et oppslag ser derfor aldri på mer enn to plasser.
+/// Er begge plassene til en ny nøkkel opptatt kastes nøkkelen som står der ut, slik gjøkungen kaster
+/// ut de andre eggene, og den utkastede nøkkelen flytter til plassen sin i den andre tabellen
+pub struct GjøkTabell {
+    tabeller: [Vec<Option<usize>>; 2],
+    /// Kapasiteten til hver av de to tabellene
+    kapasitet: usize,
+    antall_verdier: usize,
+    /// Hashfunksjonene er blandingen av nøkkelen XOR et frø, ett per tabell. Nye frø gir nye hashfunksjoner
+    frø: [u64; 2],
+    /// Tilstanden nye frø trekkes fra
+    frøkilde: u64,
+    /// Tabellen utvides før en ny nøkkel ville gitt høyere lasttall enn dette
+    maks_lasttall: f64,
+    /// Antall ganger en nøkkel er kastet ut av plassen sin
+    pub forflytninger: usize,
+    /// Antall ganger tabellen er bygget opp på nytt med nye hashfunksjoner
+    pub omhashinger: usize,
+    /// Antall omhashinger som også doblet kapasiteten
+    pub utvidelser: usize,
+}
+
+impl GjøkTabell {
+    /// Med to hashfunksjoner og én nøkkel per plass lykkes innsetting nesten alltid så lenge under
+    /// halvparten av plassene er i bruk, men blir raskt umulig over det
+    pub const STANDARD_MAKS_LASTTALL: f64 = 0.45;
+
+    /// Antall omhashinger med nye hashfunksjoner på samme kapasitet før kapasiteten dobles i stedet
+    const MAKS_FORSØK: usize = 5;
+
+    /// Lager en tabell med til sammen minst angitt antall plasser, fordelt på de to tabellene
+    pub fn new(kapasitet: usize) -> Self {
+        let kapasitet = usize::max(kapasitet.div_ceil(2), 1);
+
+        let mut tabell = Self {
+            tabeller: [vec![None; kapasitet], vec![None; kapasitet]],
+            kapasitet,
+            antall_verdier: 0,
+            frø: [0; 2],
+            frøkilde: 0,
+            maks_lasttall: Self::STANDARD_MAKS_LASTTALL,
+            forflytninger: 0,
+            omhashinger: 0,
+            utvidelser: 0,
+        };
+        tabell.frø = [tabell.nytt_frø(), tabell.nytt_frø()];
+
+        tabell
+    }
+
+    pub fn med_maks_lasttall(mut self, maks_lasttall: f64) -> Self {
+        assert!(
+            maks_lasttall > 0.0 && maks_lasttall < 1.0,
+            "Maksimalt lasttall må være større enn 0 og mindre enn 1"
+        );
+        self.maks_lasttall = maks_lasttall;
+        self
+    }
+
+    fn nytt_frø(&mut self) -> u64 {
+        self.frøkilde = self.frøkilde.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        bland(self.frøkilde)
+    }
+
+    /// Plassen nøkkelen hører til i tabell nummer `side` (0 eller 1)
+    fn hash(&self, side: usize, nøkkel: usize) -> usize {
+        (bland(nøkkel as u64 ^ self.frø[side]) % self.kapasitet as u64) as usize
+    }
+
+    /// Hvor mange ganger en nøkkel kan kastes ut på rad før vi gir opp og hasher om. Går det så mange
+    /// ganger er vi med stor sannsynlighet i en sirkel av nøkler som kaster hverandre ut
+    fn maks_forflytninger(&self) -> usize {
+        6 * (usize::BITS - self.kapasitet.leading_zeros()) as usize + 10
+    }
+
+    pub fn len(&self) -> usize {
+        self.antall_verdier
+    }
+
+    pub fn lasttall(&self) -> f64 {
+        self.antall_verdier as f64 / (2 * self.kapasitet) as f64
+    }
+
+    pub fn inneholder(&self, nøkkel: usize) -> bool {
+        (0..2).any(|side| self.tabeller[side][self.hash(side, nøkkel)] == Some(nøkkel))
+    }
+
+    /// Setter inn nøkkelen om den ikke finnes fra før, og gir om den ble satt inn
+    pub fn sett_inn(&mut self, nøkkel: usize) -> bool {
+        if self.inneholder(nøkkel) {
+            return false;
+        }
+
+        if (self.antall_verdier + 1) as f64 > self.maks_lasttall * (2 * self.kapasitet) as f64 {
+            self.hash_om(2 * self.kapasitet, Vec::new());
+        }
+
+        if let Err(hjemløs) = self.plasser(nøkkel) {
+            self.hash_om(self.kapasitet, vec![hjemløs]);
+        }
+
+        self.antall_verdier += 1;
+        true
+    }
+
+    /// Fjerner nøkkelen, og gir om den fantes
+    pub fn fjern(&mut self, nøkkel: usize) -> bool {
+        for side in 0..2 {
+            let plass = self.hash(side, nøkkel);
+            if self.tabeller[side][plass] == Some(nøkkel) {
+                self.tabeller[side][plass] = None;
+                self.antall_verdier -= 1;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Setter nøkkelen på plassen sin i første tabell, og flytter nøkkelen som eventuelt sto der til plassen
+    /// sin i den andre tabellen, osv. Gir nøkkelen som står uten plass om det ikke ordner seg innen grensen
+    fn plasser(&mut self, nøkkel: usize) -> Result<(), usize> {
+        let mut nøkkel = nøkkel;
+        let mut side = 0;
+
+        for _ in 0..self.maks_forflytninger() {
+            let plass = self.hash(side, nøkkel);
+
+            match self.tabeller[side][plass].replace(nøkkel) {
+                None => return Ok(()),
+                Some(utkastet) => {
+                    self.forflytninger += 1;
+
+                    // Den utkastede nøkkelen sto på plassen sin i denne tabellen, så den må til den andre
+                    nøkkel = utkastet;
+                    side = 1 - side;
+                }
+            }
+        }
+
+        Err(nøkkel)
+    }
+
+    /// Bygger opp tabellen på nytt med nye hashfunksjoner og angitt kapasitet, med alle nøklene den har og
+    /// de `hjemløse` nøklene som ikke fikk plass. Får ikke alle plass prøves nye hashfunksjoner, og etter
+    /// noen forsøk dobles kapasiteten også
+    fn hash_om(&mut self, kapasitet: usize, mut hjemløse: Vec<usize>) {
+        let mut nøkler: Vec<usize> = mem::take(&mut self.tabeller)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect();
+        nøkler.append(&mut hjemløse);
+
+        if kapasitet > self.kapasitet {
+            self.utvidelser += 1;
+        }
+        self.kapasitet = kapasitet;
+
+        let mut forsøk = 0;
+        'forsøk: loop {
+            self.omhashinger += 1;
+
+            forsøk += 1;
+            if forsøk > Self::MAKS_FORSØK {
+                self.kapasitet *= 2;
+                self.utvidelser += 1;
+                forsøk = 1;
+            }
+
+            self.frø = [self.nytt_frø(), self.nytt_frø()];
+            self.tabeller = [vec![None; self.kapasitet], vec![None; self.kapasitet]];
+
+            for &nøkkel in &nøkler {
+                if self.plasser(nøkkel).is_err() {
+                    continue 'forsøk;
+                }
+            }
+
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hasher_om_ved_sirkel() {
+        // To plasser i hver tabell, og tre nøkler som alle vil ha de samme to plassene, så den tredje
+        // nøkkelen bare kan sette i gang en sirkel av utkastinger
+        let mut tabell = GjøkTabell::new(4).med_maks_lasttall(0.99);
+        let plasser =
+            |tabell: &GjøkTabell, nøkkel| (tabell.hash(0, nøkkel), tabell.hash(1, nøkkel));
+        let nøkler: Vec<usize> = (0..)
+            .filter(|&nøkkel| plasser(&tabell, nøkkel) == plasser(&tabell, 0))
+            .take(3)
+            .collect();
+
+        assert!(tabell.sett_inn(nøkler[0]));
+        assert!(tabell.sett_inn(nøkler[1]));
+        assert_eq!(tabell.omhashinger, 0);
+
+        assert!(tabell.sett_inn(nøkler[2]));
+        assert!(tabell.omhashinger >= 1);
+        assert!(tabell.forflytninger > 0);
+        assert_eq!(tabell.len(), 3);
+        assert!(nøkler.iter().all(|&nøkkel| tabell.inneholder(nøkkel)));
+    }
+
+    #[test]
+    fn fjern() {
+        let mut tabell = GjøkTabell::new(16);
+        for nøkkel in 0..100 {
+            assert!(tabell.sett_inn(nøkkel));
+        }
+        assert!(!tabell.sett_inn(42));
+
+        for nøkkel in (0..100).step_by(2) {
+            assert!(tabell.fjern(nøkkel));
+            assert!(!tabell.fjern(nøkkel));
+        }
+
+        assert_eq!(tabell.len(), 50);
+        for nøkkel in 0..100 {
+            assert_eq!(tabell.inneholder(nøkkel), nøkkel % 2 == 1);
+        }
+        assert!(!tabell.fjern(1000));
+    }
+
+    #[test]
+    fn inneholder_etter_utvidelse() {
+        let mut tabell = GjøkTabell::new(4);
+        let nøkler: Vec<usize> = (0..2000).map(|i| i * 7919).collect();
+        for &nøkkel in &nøkler {
+            tabell.sett_inn(nøkkel);
+        }
+
+        assert!(tabell.utvidelser > 0);
+        assert!(tabell.lasttall() <= GjøkTabell::STANDARD_MAKS_LASTTALL);
+        assert_eq!(tabell.len(), nøkler.len());
+        assert!(nøkler.iter().all(|&nøkkel| tabell.inneholder(nøkkel)));
+        assert!(!tabell.inneholder(1));
+    }
+}
@@ -0,0 +1,488 @@
+use std::collections::HashSet;
+use std::hash::Hasher;
+
+/// En hashfunksjon tabellen kan bruke. Nøklene hashes gjennom `Hash`-traiten som vanlig, og hashfunksjonen
+/// bestemmer hva som gjøres med bytene. Plassen i tabellen er til slutt hashen modulo kapasiteten
+pub trait Hashfunksjon {
+    type Hasher: Hasher;
+
+    /// Navnet som brukes i rapporter
+    fn navn(&self) -> &'static str;
+
+    /// En ny hasher for én nøkkel, i en tabell med angitt kapasitet
+    fn hasher(&self, kapasitet: usize) -> Self::Hasher;
+}
+
+/// Blander bitene i et 64-bits tall så hver bit i input påvirker alle bitene i svaret (splitmix64)
+pub fn bland(tall: u64) -> u64 {
+    let mut z = tall;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Polynomet tabellen alltid har brukt: hver bokstav vektes med 5^(n - i), der i er plassen til
+/// bokstaven og n er lengden, og summen holdes under kapasiteten underveis.
+///
+/// Gjennom `Hash` avsluttes en str med byten 0xff, så "ab" + "c" og "a" + "bc" blir ulike i tupler,
+/// og den kommer med som et siste ledd. Er kapasiteten et primtall annet enn 5 stokker det bare om
+/// på plassene, så de samme nøklene kolliderer som med polynomet alene
+#[derive(Clone, Copy, Default)]
+pub struct Polynom;
+
+pub struct Polynomhasher {
+    hash: u64,
+    kapasitet: u64,
+}
+
+impl Polynomhasher {
+    fn legg_til(&mut self, verdi: u64) {
+        self.hash = (5 * self.hash + verdi) % self.kapasitet;
+    }
+}
+
+impl Hasher for Polynomhasher {
+    fn write(&mut self, bytes: &[u8]) {
+        // Strenger hashes bokstav for bokstav og ikke byte for byte, så æ, ø og å teller som én bokstav
+        match std::str::from_utf8(bytes) {
+            Ok(tekst) => tekst
+                .chars()
+                .for_each(|bokstav| self.legg_til(bokstav as u64)),
+            Err(_) => bytes.iter().for_each(|&byte| self.legg_til(byte as u64)),
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl Hashfunksjon for Polynom {
+    type Hasher = Polynomhasher;
+
+    fn navn(&self) -> &'static str {
+        "polynom"
+    }
+
+    fn hasher(&self, kapasitet: usize) -> Self::Hasher {
+        Polynomhasher {
+            hash: 0,
+            kapasitet: kapasitet as u64,
+        }
+    }
+}
+
+/// 64-bits FNV-1a: hver byte XOR-es inn og ganges så med et primtall, så alle bitene i byten sprer seg oppover
+#[derive(Clone, Copy, Default)]
+pub struct Fnv1a;
+
+pub struct Fnv1aHasher {
+    hash: u64,
+}
+
+impl Hasher for Fnv1aHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.hash ^= byte as u64;
+            self.hash = self.hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl Hashfunksjon for Fnv1a {
+    type Hasher = Fnv1aHasher;
+
+    fn navn(&self) -> &'static str {
+        "fnv-1a"
+    }
+
+    fn hasher(&self, _kapasitet: usize) -> Self::Hasher {
+        Fnv1aHasher {
+            hash: 0xcbf2_9ce4_8422_2325,
+        }
+    }
+}
+
+/// Dan Bernsteins djb2: hash * 33 + byte, med startverdi 5381
+#[derive(Clone, Copy, Default)]
+pub struct Djb2;
+
+pub struct Djb2Hasher {
+    hash: u64,
+}
+
+impl Hasher for Djb2Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.hash = self.hash.wrapping_mul(33).wrapping_add(byte as u64);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl Hashfunksjon for Djb2 {
+    type Hasher = Djb2Hasher;
+
+    fn navn(&self) -> &'static str {
+        "djb2"
+    }
+
+    fn hasher(&self, _kapasitet: usize) -> Self::Hasher {
+        Djb2Hasher { hash: 5381 }
+    }
+}
+
+/// 32-bits MurmurHash3 (x86_32) med angitt frø
+#[derive(Clone, Copy, Default)]
+pub struct Murmur3 {
+    pub frø: u32,
+}
+
+/// Murmur3 blander inn fire byter av gangen, så bytene samles opp til hashen skal regnes ut
+pub struct Murmur3Hasher {
+    frø: u32,
+    bytes: Vec<u8>,
+}
+
+fn murmur3_32(data: &[u8], frø: u32) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+
+    let bland = |k: u32| k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+
+    let mut hash = frø;
+    let mut blokker = data.chunks_exact(4);
+
+    for blokk in &mut blokker {
+        hash ^= bland(u32::from_le_bytes(blokk.try_into().unwrap()));
+        hash = hash
+            .rotate_left(13)
+            .wrapping_mul(5)
+            .wrapping_add(0xe654_6b64);
+    }
+
+    // De siste 1-3 bytene blandes inn uten den siste rotasjonen
+    let rest = blokker.remainder();
+    if !rest.is_empty() {
+        let k = rest
+            .iter()
+            .rev()
+            .fold(0_u32, |k, &byte| (k << 8) | byte as u32);
+        hash ^= bland(k);
+    }
+
+    // Avsluttende miksing, så hver bit i input påvirker alle bitene i hashen
+    hash ^= data.len() as u32;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^= hash >> 16;
+
+    hash
+}
+
+impl Hasher for Murmur3Hasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        murmur3_32(&self.bytes, self.frø) as u64
+    }
+}
+
+impl Hashfunksjon for Murmur3 {
+    type Hasher = Murmur3Hasher;
+
+    fn navn(&self) -> &'static str {
+        "murmur3"
+    }
+
+    fn hasher(&self, _kapasitet: usize) -> Self::Hasher {
+        Murmur3Hasher {
+            frø: self.frø,
+            bytes: Vec::new(),
+        }
+    }
+}
+
+/// SipHash-2-4 med en 128-bits nøkkel. Med en hemmelig nøkkel kan ikke en angriper regne ut på forhånd
+/// hvilke nøkler som kolliderer, og det er derfor Rust sin egen HashMap bruker en variant av den
+#[derive(Clone, Copy, Default)]
+pub struct SipHash {
+    pub k0: u64,
+    pub k1: u64,
+}
+
+/// SipHash blander inn åtte byter av gangen, så bytene samles opp til hashen skal regnes ut
+pub struct SipHasher {
+    k0: u64,
+    k1: u64,
+    bytes: Vec<u8>,
+}
+
+fn siphash_2_4(data: &[u8], k0: u64, k1: u64) -> u64 {
+    let mut v = [
+        k0 ^ 0x736f_6d65_7073_6575,
+        k1 ^ 0x646f_7261_6e64_6f6d,
+        k0 ^ 0x6c79_6765_6e65_7261,
+        k1 ^ 0x7465_6462_7974_6573,
+    ];
+
+    let runde = |v: &mut [u64; 4]| {
+        v[0] = v[0].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(13) ^ v[0];
+        v[0] = v[0].rotate_left(32);
+        v[2] = v[2].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(16) ^ v[2];
+        v[0] = v[0].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(21) ^ v[0];
+        v[2] = v[2].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(17) ^ v[2];
+        v[2] = v[2].rotate_left(32);
+    };
+
+    let bland_inn = |v: &mut [u64; 4], m: u64| {
+        v[3] ^= m;
+        runde(v);
+        runde(v);
+        v[0] ^= m;
+    };
+
+    let mut blokker = data.chunks_exact(8);
+    for blokk in &mut blokker {
+        bland_inn(&mut v, u64::from_le_bytes(blokk.try_into().unwrap()));
+    }
+
+    // Siste blokk er de resterende bytene, med lengden modulo 256 i den øverste byten
+    let siste = blokker
+        .remainder()
+        .iter()
+        .rev()
+        .fold(0, |m, &byte| (m << 8) | byte as u64)
+        | (data.len() as u64) << 56;
+    bland_inn(&mut v, siste);
+
+    v[2] ^= 0xff;
+    for _ in 0..4 {
+        runde(&mut v);
+    }
+
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+impl Hasher for SipHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        siphash_2_4(&self.bytes, self.k0, self.k1)
+    }
+}
+
+impl Hashfunksjon for SipHash {
+    type Hasher = SipHasher;
+
+    fn navn(&self) -> &'static str {
+        "siphash"
+    }
+
+    fn hasher(&self, _kapasitet: usize) -> Self::Hasher {
+        SipHasher {
+            k0: self.k0,
+            k1: self.k1,
+            bytes: Vec::new(),
+        }
+    }
+}
+
+/// Hvor godt en hashfunksjon fordelte navnene utover tabellen
+struct Fordeling {
+    navn: &'static str,
+    kollisjoner: usize,
+    lengste_kjede: usize,
+    tomme_plasser: usize,
+    kjikvadrat: f64,
+}
+
+/// Plassen strengen får med hashfunksjonen. Bare bytene i strengen hashes, uten 0xff-en `Hash` for str
+/// legger til, så hashen er den samme som i referansene til hashfunksjonen
+fn plass<H: Hashfunksjon>(hashfunksjon: &H, streng: &str, kapasitet: usize) -> usize {
+    let mut hasher = hashfunksjon.hasher(kapasitet);
+    hasher.write(streng.as_bytes());
+
+    (hasher.finish() % kapasitet as u64) as usize
+}
+
+/// Fordeler navnene på plassene i en tabell med angitt hashfunksjon, og måler fordelingen.
+/// Et navn som står flere ganger telles bare én gang, slik det ville blitt i en hashtabell
+fn fordeling<H: Hashfunksjon>(
+    navneliste: &[String],
+    kapasitet: usize,
+    hashfunksjon: H,
+) -> Fordeling {
+    // Kapasiteten er den samme uansett hvor mange navn det er, så alle hashfunksjonene måles likt
+    let mut kjedelengder = vec![0_usize; kapasitet];
+    let mut kollisjoner = 0;
+    let mut sett = HashSet::new();
+
+    for navn in navneliste.iter().filter(|navn| sett.insert(navn.as_str())) {
+        let plass = plass(&hashfunksjon, navn, kapasitet);
+        if kjedelengder[plass] > 0 {
+            kollisjoner += 1;
+        }
+        kjedelengder[plass] += 1;
+    }
+
+    // Med n nøkler fordelt jevnt på m plasser er forventet kjedelengde n / m. Kji-kvadrat summerer
+    // det kvadrerte avviket fra dette over alle plassene, og er omtrent m - 1 for en tilfeldig fordeling
+    let forventet = sett.len() as f64 / kapasitet as f64;
+    let kjikvadrat = kjedelengder
+        .iter()
+        .map(|&lengde| (lengde as f64 - forventet).powi(2) / forventet)
+        .sum();
+
+    Fordeling {
+        navn: hashfunksjon.navn(),
+        kollisjoner,
+        lengste_kjede: kjedelengder.iter().copied().max().unwrap_or(0),
+        tomme_plasser: kjedelengder.iter().filter(|&&lengde| lengde == 0).count(),
+        kjikvadrat,
+    }
+}
+
+/// Sammenligner alle hashfunksjonene på samme navneliste og kapasitet, og gir resultatet som en tabell
+pub fn sammenligning(navneliste: &[String], kapasitet: usize) -> String {
+    let fordelinger = [
+        fordeling(navneliste, kapasitet, Polynom),
+        fordeling(navneliste, kapasitet, Fnv1a),
+        fordeling(navneliste, kapasitet, Djb2),
+        fordeling(navneliste, kapasitet, Murmur3::default()),
+        fordeling(navneliste, kapasitet, SipHash::default()),
+    ];
+
+    let mut rapport = format!(
+        "{} navn i en tabell med kapasitet {kapasitet}\n\n{:<10}{:>13}{:>15}{:>15}{:>13}\n",
+        navneliste.len(),
+        "Hash",
+        "Kollisjoner",
+        "Lengste kjede",
+        "Tomme plasser",
+        "Kji-kvadrat"
+    );
+
+    for fordeling in fordelinger {
+        rapport.push_str(&format!(
+            "{:<10}{:>13}{:>15}{:>15}{:>13.1}\n",
+            fordeling.navn,
+            fordeling.kollisjoner,
+            fordeling.lengste_kjede,
+            fordeling.tomme_plasser,
+            fordeling.kjikvadrat
+        ));
+    }
+
+    // Kji-kvadrat med m - 1 frihetsgrader har forventning m - 1 og standardavvik sqrt(2 (m - 1))
+    let frihetsgrader = kapasitet.saturating_sub(1) as f64;
+    rapport.push_str(&format!(
+        "\nVed tilfeldig fordeling er kji-kvadrat omtrent {frihetsgrader} ± {:.1}. \
+Mye høyere betyr at noen plasser får for mange navn",
+        (2.0 * frihetsgrader).sqrt()
+    ));
+
+    rapport
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::Hash;
+
+    use super::*;
+
+    fn hash_av<H: Hashfunksjon, T: Hash + ?Sized>(hashfunksjon: &H, verdi: &T) -> u64 {
+        let mut hasher = hashfunksjon.hasher(1 << 20);
+        verdi.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn bytehash<H: Hashfunksjon>(hashfunksjon: &H, bytes: &[u8]) -> u64 {
+        let mut hasher = hashfunksjon.hasher(1 << 20);
+        hasher.write(bytes);
+        hasher.finish()
+    }
+
+    /// Sjekker at en nøkkel som skriver nøyaktig byten 0xff ikke hashes som om den var tom
+    fn skiller_0xff<H: Hashfunksjon>(hashfunksjon: H) {
+        let navn = hashfunksjon.navn();
+
+        assert_ne!(
+            hash_av(&hashfunksjon, &255_u8),
+            hash_av(&hashfunksjon, &()),
+            "{navn}"
+        );
+        assert_ne!(
+            hash_av(&hashfunksjon, &255_u8),
+            hash_av(&hashfunksjon, &0_u8),
+            "{navn}"
+        );
+        assert_ne!(
+            hash_av(&hashfunksjon, &(1_u8, 255_u8)),
+            hash_av(&hashfunksjon, &1_u8),
+            "{navn}"
+        );
+    }
+
+    #[test]
+    fn byten_0xff_hashes_som_andre_byter() {
+        skiller_0xff(Polynom);
+        skiller_0xff(Fnv1a);
+        skiller_0xff(Djb2);
+        skiller_0xff(Murmur3::default());
+        skiller_0xff(SipHash::default());
+    }
+
+    #[test]
+    fn bytehash_stemmer_med_referansene() {
+        assert_eq!(bytehash(&Fnv1a, b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(bytehash(&Fnv1a, b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(bytehash(&Djb2, b"a"), 5381 * 33 + 97);
+        assert_eq!(bytehash(&Murmur3::default(), b""), 0);
+        assert_eq!(bytehash(&Murmur3 { frø: 1 }, b""), 0x514e_28b7);
+        assert_eq!(bytehash(&Murmur3::default(), b"test"), 0xba6b_d213);
+
+        let sip = SipHash {
+            k0: 0x0706_0504_0302_0100,
+            k1: 0x0f0e_0d0c_0b0a_0908,
+        };
+        assert_eq!(bytehash(&sip, b""), 0x726f_db47_dd0e_0e31);
+    }
+
+    #[test]
+    fn polynom_vekter_bokstavene() {
+        assert_eq!(plass(&Polynom, "ab", 127), (5 * 97 + 98) % 127);
+        // æ er én bokstav, ikke to byter
+        assert_eq!(plass(&Polynom, "æ", 1000), 'æ' as usize % 1000);
+        assert_ne!(plass(&Polynom, "ab", 127), plass(&Polynom, "ba", 127));
+    }
+
+    #[test]
+    fn fordeling_teller_hvert_navn_én_gang() {
+        // Med kapasitet 2 havner "ab" og "ba" begge på plass 1, og "d" på plass 0
+        let navneliste: Vec<String> = ["ab", "ab", "ba", "d"].map(String::from).to_vec();
+        let fordeling = fordeling(&navneliste, 2, Polynom);
+
+        assert_eq!(plass(&Polynom, "ab", 2), plass(&Polynom, "ba", 2));
+        assert_eq!(fordeling.kollisjoner, 1);
+        assert_eq!(fordeling.lengste_kjede, 2);
+        assert_eq!(fordeling.tomme_plasser, 0);
+    }
+}
@@ -1,10 +1,25 @@
+use std::borrow::Borrow;
 use std::collections::{HashMap, LinkedList};
 use std::env::args;
 use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
 use std::io::{BufRead, BufReader, Read};
+use std::mem;
 use std::process::exit;
 use std::time::{Duration, Instant};
 
+mod gjoktabell;
+mod hashfunksjoner;
+mod kollisjoner;
+mod perfekt;
+mod probing;
+
+use gjoktabell::GjøkTabell;
+use hashfunksjoner::{Hashfunksjon, Polynom};
+use kollisjoner::{Kollisjon, Kollisjonsobservatør, Logg, Teller, Utskrift};
+use perfekt::{PerfektHash, PerfektTabell};
+use probing::{Probesekvens, Probestatistikk, Probing};
+
 fn fil_til_vektor<T: Into<String> + Copy>(filnavn: T) -> Result<Vec<String>, ()> {
     let fil = match fs::File::open(filnavn.into()) {
         Ok(fil) => fil,
@@ -54,84 +69,308 @@ fn tilfeldige_heltall(antall: usize, max: usize) -> Vec<usize> {
     // Bruker bit-forskyvning til å generere verdier av korrekt størrelse,
     // basert på antall bits vi har plass til i verdiene som skal returneres
     for i in 0..antall {
-        tall[i] = (filbuffer[bredde * i] as usize)
-            | (filbuffer[bredde * i + 1] as usize) << 8
+        tall[i] = ((filbuffer[bredde * i] as usize)
+            | ((filbuffer[bredde * i + 1] as usize) << 8)
             | if bredde > 2 {
-                (filbuffer[bredde * i + 2] as usize) << 16
-                    | (filbuffer[bredde * i + 3] as usize) << 24
+                ((filbuffer[bredde * i + 2] as usize) << 16)
+                    | ((filbuffer[bredde * i + 3] as usize) << 24)
                     | if bredde > 4 {
-                        (filbuffer[bredde * i + 4] as usize) << 32
-                            | (filbuffer[bredde * i + 5] as usize) << 40
-                            | (filbuffer[bredde * i + 6] as usize) << 48
-                            | (filbuffer[bredde * i + 7] as usize) << 56
+                        ((filbuffer[bredde * i + 4] as usize) << 32)
+                            | ((filbuffer[bredde * i + 5] as usize) << 40)
+                            | ((filbuffer[bredde * i + 6] as usize) << 48)
+                            | ((filbuffer[bredde * i + 7] as usize) << 56)
                     } else {
                         0
                     }
             } else {
                 0
-            } % max;
+            })
+            % max;
     }
 
     tall
 }
 
-struct HashTabell {
-    tabell: Vec<LinkedList<String>>,
+/// Antall plasser fra den gamle tabellen som flyttes over for hver innsetting under inkrementell omhashing
+const PLASSER_PER_STEG: usize = 8;
+
+fn er_primtall(n: usize) -> bool {
+    n >= 2
+        && (2..)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
+
+/// Første primtall som er større enn eller lik `n`
+fn neste_primtall(n: usize) -> usize {
+    (n..).find(|&n| er_primtall(n)).unwrap()
+}
+
+/// Hashtabell med nøkler og verdier, der kollisjoner håndteres ved at hver plass i tabellen
+/// er en koblet liste med alle nøkkel/verdi-parene som hashet dit
+struct HashTabell<K, V, H = Polynom> {
+    tabell: Vec<LinkedList<(K, V)>>,
     kapasitet: usize,
     antall_verdier: usize,
     kollisjoner: usize,
+    /// Tabellen utvides før en ny nøkkel ville gitt høyere lasttall enn dette
+    maks_lasttall: f32,
+    /// Om innholdet flyttes over til en utvidet tabell litt av gangen, i stedet for alt på en gang
+    inkrementell: bool,
+    /// Antall ganger tabellen er utvidet
+    omhashinger: usize,
+    /// Tabellen fra før siste utvidelse, mens innholdet flyttes over litt av gangen. Tom ellers
+    gammel: Vec<LinkedList<(K, V)>>,
+    /// Neste plass i den gamle tabellen som skal flyttes over
+    flyttet: usize,
+    hashfunksjon: H,
 }
 
-impl HashTabell {
+impl<K: Hash + Eq, V> HashTabell<K, V> {
     pub fn new(kapasitet: usize) -> Self {
-        let mut tabell: Vec<LinkedList<String>> = Vec::new();
-        tabell.resize(kapasitet, LinkedList::new());
+        Self::med_hashfunksjon(kapasitet, Polynom)
+    }
+}
+
+impl<K: Hash + Eq, V, H: Hashfunksjon> HashTabell<K, V, H> {
+    /// Med lenkede lister kan det ligge flere nøkler enn det er plasser, så som standard utvides
+    /// tabellen først når den ellers ville fått flere nøkler enn plasser
+    const STANDARD_MAKS_LASTTALL: f32 = 1.0;
+
+    /// Kapasiteten blir minst 1, siden plassen til en nøkkel er hashen modulo kapasiteten
+    pub fn med_hashfunksjon(kapasitet: usize, hashfunksjon: H) -> Self {
+        let kapasitet = kapasitet.max(1);
+        let tabell = (0..kapasitet).map(|_| LinkedList::new()).collect();
 
         Self {
             tabell,
             kapasitet,
             antall_verdier: 0,
             kollisjoner: 0,
+            maks_lasttall: Self::STANDARD_MAKS_LASTTALL,
+            inkrementell: false,
+            omhashinger: 0,
+            gammel: Vec::new(),
+            flyttet: 0,
+            hashfunksjon,
         }
     }
 
-    fn hash(&self, nøkkel: &String) -> usize {
-        let mut hash: usize = 0;
+    pub fn med_maks_lasttall(mut self, maks_lasttall: f32) -> Self {
+        assert!(
+            maks_lasttall > 0.0,
+            "Maksimalt lasttall må være større enn 0"
+        );
+        self.maks_lasttall = maks_lasttall;
+        self
+    }
+
+    /// Flytt innholdet over til en utvidet tabell litt av gangen, så ingen enkelt innsetting må vente på
+    /// at hele tabellen hashes om. Oppslag må da se i både den nye og den gamle tabellen til flyttingen er ferdig
+    pub fn med_inkrementell_omhashing(mut self, inkrementell: bool) -> Self {
+        self.inkrementell = inkrementell;
+        self
+    }
+
+    /// Plassen en nøkkel hører til i en tabell med angitt kapasitet. Tar imot alt nøkkelen kan lånes som
+    /// (f.eks. &str for String), siden slike typer skal hashe likt som nøkkelen selv
+    fn hash_med_kapasitet<Q: Hash + ?Sized>(&self, nøkkel: &Q, kapasitet: usize) -> usize {
+        let mut hasher = self.hashfunksjon.hasher(kapasitet);
+        nøkkel.hash(&mut hasher);
+
+        (hasher.finish() % kapasitet as u64) as usize
+    }
+
+    fn hash<Q: Hash + ?Sized>(&self, nøkkel: &Q) -> usize {
+        self.hash_med_kapasitet(nøkkel, self.kapasitet)
+    }
+
+    /// Utvider tabellen til første primtall etter dobbel kapasitet, om en ny nøkkel ville gitt for høyt lasttall
+    fn voks_ved_behov(&mut self) {
+        if (self.antall_verdier + 1) as f32 <= self.maks_lasttall * self.kapasitet as f32 {
+            return;
+        }
 
-        // Genererer hash ved å iterere gjennom bokstavene i strengen, med ulik vekting for hver bokstav (5^i)
-        for bokstav in nøkkel.chars() {
-            hash = (5 * hash) + bokstav as usize;
-            hash = hash % self.kapasitet;
+        // En tidligere utvidelse må være ferdig flyttet før neste kan begynne
+        self.flytt_over(self.gammel.len());
+
+        self.kapasitet = neste_primtall(2 * self.kapasitet);
+        let ny = (0..self.kapasitet).map(|_| LinkedList::new()).collect();
+        self.gammel = mem::replace(&mut self.tabell, ny);
+        self.omhashinger += 1;
+
+        if !self.inkrementell {
+            self.flytt_over(self.gammel.len());
         }
+    }
+
+    /// Flytter inntil `antall` plasser fra den gamle tabellen over i den nye
+    fn flytt_over(&mut self, antall: usize) {
+        let slutt = usize::min(self.flyttet + antall, self.gammel.len());
+        for indeks in self.flyttet..slutt {
+            self.flytt_plass(indeks);
+        }
+        self.flyttet = slutt;
+
+        if self.flyttet == self.gammel.len() {
+            self.gammel = Vec::new();
+            self.flyttet = 0;
+        }
+    }
+
+    /// Hasher om alle nøklene på angitt plass i den gamle tabellen, og legger dem inn i den nye
+    fn flytt_plass(&mut self, indeks: usize) {
+        for (nøkkel, verdi) in mem::take(&mut self.gammel[indeks]) {
+            let ny_indeks = self.hash(&nøkkel);
+            self.tabell[ny_indeks].push_front((nøkkel, verdi));
+        }
+    }
+
+    /// Gjør klar for en endring av nøkkelen: under inkrementell omhashing flyttes plassen nøkkelen hadde
+    /// i den gamle tabellen over med en gang, så den bare trenger å letes etter i den nye, og flyttingen
+    /// kommer et steg videre
+    fn forbered<Q: Hash + ?Sized>(&mut self, nøkkel: &Q) {
+        if !self.gammel.is_empty() {
+            self.flytt_plass(self.hash_med_kapasitet(nøkkel, self.gammel.len()));
+            self.flytt_over(PLASSER_PER_STEG);
+        }
+    }
+
+    /// Plassen i tabellen og plassen i den koblede listen der nøkkelen ligger, om den finnes
+    fn finn<Q>(&self, nøkkel: &Q) -> (usize, Option<usize>)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let indeks = self.hash(nøkkel);
+        let posisjon = self.tabell[indeks]
+            .iter()
+            .position(|(annen, _)| annen.borrow() == nøkkel);
+
+        (indeks, posisjon)
+    }
+
+    /// Setter inn en ny nøkkel på angitt plass i tabellen, og teller en kollisjon om plassen var i bruk fra før
+    fn sett_inn_ny(&mut self, indeks: usize, nøkkel: K, verdi: V) -> &mut V {
+        if !self.tabell[indeks].is_empty() {
+            self.kollisjoner += 1;
+        }
+
+        self.antall_verdier += 1;
+        self.tabell[indeks].push_front((nøkkel, verdi));
+
+        &mut self.tabell[indeks].front_mut().unwrap().1
+    }
+
+    /// Setter inn en verdi for nøkkelen. Fantes nøkkelen fra før byttes verdien ut, og den gamle returneres
+    pub fn insert(&mut self, nøkkel: K, verdi: V) -> Option<V> {
+        match self.entry(nøkkel) {
+            Entry::Occupied(mut entry) => Some(entry.insert(verdi)),
+            Entry::Vacant(entry) => {
+                entry.insert(verdi);
+                None
+            }
+        }
+    }
 
-        hash
+    /// Nøkkel/verdi-paret for nøkkelen, i den nye tabellen eller i den gamle om omhashing pågår
+    fn finn_par<Q>(&self, nøkkel: &Q) -> Option<&(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let gammel = (!self.gammel.is_empty())
+            .then(|| &self.gammel[self.hash_med_kapasitet(nøkkel, self.gammel.len())]);
+
+        self.tabell[self.hash(nøkkel)]
+            .iter()
+            .chain(gammel.into_iter().flatten())
+            .find(|(annen, _)| annen.borrow() == nøkkel)
+    }
+
+    pub fn get<Q>(&self, nøkkel: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.finn_par(nøkkel).map(|(_, verdi)| verdi)
+    }
+
+    pub fn get_mut<Q>(&mut self, nøkkel: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.forbered(nøkkel);
+        let (indeks, posisjon) = self.finn(nøkkel);
+        posisjon.map(|posisjon| &mut self.tabell[indeks].iter_mut().nth(posisjon).unwrap().1)
+    }
+
+    pub fn inneholder<Q>(&self, nøkkel: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.finn_par(nøkkel).is_some()
+    }
+
+    /// Fjerner nøkkelen fra tabellen, og returnerer verdien om den fantes
+    pub fn remove<Q>(&mut self, nøkkel: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.forbered(nøkkel);
+        let (indeks, posisjon) = self.finn(nøkkel);
+        posisjon.map(|posisjon| self.fjern_fra(indeks, posisjon).1)
+    }
+
+    /// Tar ut paret på angitt plass i den koblede listen på angitt plass i tabellen
+    fn fjern_fra(&mut self, indeks: usize, posisjon: usize) -> (K, V) {
+        // En koblet liste kan ikke fjerne fra midten direkte, så den deles i to og settes sammen igjen
+        let liste = &mut self.tabell[indeks];
+        let mut resten = liste.split_off(posisjon);
+        let par = resten.pop_front().unwrap();
+        liste.append(&mut resten);
+
+        self.antall_verdier -= 1;
+        par
     }
 
-    pub fn sett_inn(&mut self, streng: String) -> () {
-        // Beregn hash
-        let hash = Self::hash(&self, &streng);
+    /// Plassen til nøkkelen i tabellen, enten den er fylt eller ledig. Finnes ikke nøkkelen utvides
+    /// tabellen først om det trengs, så plassen den får fortsatt er riktig når den settes inn
+    pub fn entry(&mut self, nøkkel: K) -> Entry<'_, K, V, H> {
+        self.forbered(&nøkkel);
 
-        // Hvis listen på den angitte plassen allerede har innhold er det en kollisjon
-        if !self.tabell[hash].is_empty() {
-            // Tell kollisjonen
-            self.kollisjoner = self.kollisjoner + 1;
+        match self.finn(&nøkkel) {
+            (indeks, Some(posisjon)) => Entry::Occupied(OccupiedEntry {
+                tabell: self,
+                indeks,
+                posisjon,
+            }),
+            (_, None) => {
+                self.voks_ved_behov();
+                let indeks = self.hash(&nøkkel);
 
-            // Og print den
-            print!("{} kolliderte med", streng);
-            for navn in &self.tabell[hash] {
-                print!(" {navn},");
+                Entry::Vacant(VacantEntry {
+                    tabell: self,
+                    nøkkel,
+                    indeks,
+                })
             }
-            println!();
         }
+    }
 
-        // Kollisjonen håndteres av den koblede listen, så innsetting kan gjøres uavhengig av om en kollisjon oppsto
-        self.tabell[hash].push_front(streng);
-        self.antall_verdier = self.antall_verdier + 1;
+    /// Alle nøkkel/verdi-parene, i den rekkefølgen de ligger i tabellen
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.tabell
+            .iter()
+            .chain(&self.gammel)
+            .flat_map(|liste| liste.iter().map(|(nøkkel, verdi)| (nøkkel, verdi)))
     }
 
-    pub fn inneholder(&self, streng: String) -> bool {
-        // Sjekker om listen på den angitte plassen inneholder elementet vi ser etter
-        self.tabell[self.hash(&streng)].contains(&streng)
+    pub fn len(&self) -> usize {
+        self.antall_verdier
     }
 
     pub fn lasttall(&self) -> f32 {
@@ -139,64 +378,625 @@ impl HashTabell {
     }
 }
 
+impl<K: Hash + Eq, V, H: Hashfunksjon> HashTabell<K, V, H> {
+    /// Som `insert`, men gir observatøren beskjed om hvilke nøkler en ny nøkkel kolliderer med
+    pub fn sett_inn<O: Kollisjonsobservatør<K> + ?Sized>(
+        &mut self,
+        nøkkel: K,
+        verdi: V,
+        observatør: &mut O,
+    ) -> Option<V> {
+        match self.entry(nøkkel) {
+            Entry::Occupied(mut entry) => Some(entry.insert(verdi)),
+            Entry::Vacant(entry) => {
+                // Hvis listen på den angitte plassen allerede har innhold er det en kollisjon
+                let liste = &entry.tabell.tabell[entry.indeks];
+                if !liste.is_empty() {
+                    observatør.kollisjon(&Kollisjon {
+                        nøkkel: &entry.nøkkel,
+                        plass: entry.indeks,
+                        andre: liste.iter().map(|(annen, _)| annen).collect(),
+                    });
+                }
+
+                entry.insert(verdi);
+                None
+            }
+        }
+    }
+}
+
+/// En plass i tabellen for en bestemt nøkkel, som enten har en verdi fra før eller er ledig
+pub enum Entry<'a, K, V, H = Polynom> {
+    Occupied(OccupiedEntry<'a, K, V, H>),
+    Vacant(VacantEntry<'a, K, V, H>),
+}
+
+/// En nøkkel som allerede finnes i tabellen
+pub struct OccupiedEntry<'a, K, V, H = Polynom> {
+    tabell: &'a mut HashTabell<K, V, H>,
+    /// Plassen i tabellen
+    indeks: usize,
+    /// Plassen i den koblede listen på plass `indeks`
+    posisjon: usize,
+}
+
+/// En nøkkel som ikke finnes i tabellen, med plassen den hører hjemme om den settes inn
+pub struct VacantEntry<'a, K, V, H = Polynom> {
+    tabell: &'a mut HashTabell<K, V, H>,
+    nøkkel: K,
+    indeks: usize,
+}
+
+impl<'a, K: Hash + Eq, V, H: Hashfunksjon> Entry<'a, K, V, H> {
+    /// Gir verdien for nøkkelen, og setter inn `standard` først om nøkkelen ikke finnes
+    pub fn or_insert(self, standard: V) -> &'a mut V {
+        self.or_insert_with(|| standard)
+    }
+
+    /// Som `or_insert`, men verdien som settes inn regnes bare ut om nøkkelen ikke finnes
+    pub fn or_insert_with<F: FnOnce() -> V>(self, standard: F) -> &'a mut V {
+        match self {
+            Self::Occupied(entry) => entry.into_mut(),
+            Self::Vacant(entry) => entry.insert(standard()),
+        }
+    }
+
+    /// Endrer verdien om nøkkelen finnes, og lar en ledig plass være som den er
+    pub fn and_modify<F: FnOnce(&mut V)>(self, endring: F) -> Self {
+        match self {
+            Self::Occupied(mut entry) => {
+                endring(entry.get_mut());
+                Self::Occupied(entry)
+            }
+            Self::Vacant(entry) => Self::Vacant(entry),
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Occupied(entry) => entry.key(),
+            Self::Vacant(entry) => &entry.nøkkel,
+        }
+    }
+}
+
+impl<'a, K: Hash + Eq, V: Default, H: Hashfunksjon> Entry<'a, K, V, H> {
+    /// Som `or_insert`, men bruker standardverdien til `V`
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K: Hash + Eq, V, H: Hashfunksjon> OccupiedEntry<'a, K, V, H> {
+    fn par(&self) -> &(K, V) {
+        self.tabell.tabell[self.indeks]
+            .iter()
+            .nth(self.posisjon)
+            .unwrap()
+    }
+
+    pub fn key(&self) -> &K {
+        &self.par().0
+    }
+
+    pub fn get(&self) -> &V {
+        &self.par().1
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.tabell.tabell[self.indeks]
+            .iter_mut()
+            .nth(self.posisjon)
+            .unwrap()
+            .1
+    }
+
+    /// Gjør om til en muterbar referanse til verdien, som lever like lenge som tabellen er lånt
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.tabell.tabell[self.indeks]
+            .iter_mut()
+            .nth(self.posisjon)
+            .unwrap()
+            .1
+    }
+
+    /// Bytter ut verdien, og returnerer den gamle
+    pub fn insert(&mut self, verdi: V) -> V {
+        mem::replace(self.get_mut(), verdi)
+    }
+
+    /// Fjerner nøkkelen fra tabellen, og returnerer verdien
+    pub fn remove(self) -> V {
+        self.tabell.fjern_fra(self.indeks, self.posisjon).1
+    }
+}
+
+impl<'a, K: Hash + Eq, V, H: Hashfunksjon> VacantEntry<'a, K, V, H> {
+    pub fn key(&self) -> &K {
+        &self.nøkkel
+    }
+
+    /// Setter inn verdien for nøkkelen
+    pub fn insert(self, verdi: V) -> &'a mut V {
+        self.tabell.sett_inn_ny(self.indeks, self.nøkkel, verdi)
+    }
+}
+
+/// En plass i tabellen med åpen adressering
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Plass {
+    Ledig,
+    /// En gravstein der en nøkkel er fjernet. Oppslag må lete videre forbi den, siden nøkler som kolliderte
+    /// med den fjernede nøkkelen kan stå lenger ut, men innsetting kan bruke plassen på nytt
+    Slettet,
+    Opptatt(usize),
+}
+
 struct HashTabell2 {
-    tabell: Vec<Option<usize>>,
+    tabell: Vec<Plass>,
     kapasitet: usize,
     antall_verdier: usize,
     kollisjoner: usize,
+    /// Antall gravsteiner i tabellen
+    gravsteiner: usize,
+    /// Tabellen hashes om før en ny nøkkel ville gitt høyere lasttall enn dette. Aldri over 1,
+    /// siden en full tabell ikke har noen ledig plass å prøve seg frem til
+    maks_lasttall: f64,
+    /// Om innholdet flyttes over til en utvidet tabell litt av gangen, i stedet for alt på en gang
+    inkrementell: bool,
+    /// Antall ganger tabellen er hashet om
+    omhashinger: usize,
+    /// Tabellen fra før siste omhashing, mens innholdet flyttes over litt av gangen. Tom ellers
+    gammel: Vec<Plass>,
+    /// Neste plass i den gamle tabellen som skal flyttes over
+    flyttet: usize,
+    probing: Probing,
 }
 
 impl HashTabell2 {
+    /// Med åpen adressering blir hver innsetting raskt dyrere når tabellen nærmer seg full
+    const STANDARD_MAKS_LASTTALL: f64 = 0.9;
+
+    /// Første primtall på formen 4k + 3 som er minst `n`. Dobbel hashing trenger en primtallskapasitet
+    /// for at hopplengden aldri skal dele kapasiteten, og kvadratisk probing trenger i tillegg formen
+    /// 4k + 3 for å komme innom alle plassene
+    fn neste_kapasitet(n: usize) -> usize {
+        (n..).find(|&n| n % 4 == 3 && er_primtall(n)).unwrap()
+    }
+
+    /// Lager en tabell med minst angitt kapasitet, rundet opp til en kapasitet alle probingene virker med
     pub fn new(kapasitet: usize) -> Self {
-        let mut tabell: Vec<Option<usize>> = Vec::new();
-        tabell.resize(kapasitet, None);
+        let kapasitet = Self::neste_kapasitet(kapasitet);
 
         Self {
-            tabell,
+            tabell: vec![Plass::Ledig; kapasitet],
             kapasitet,
             antall_verdier: 0,
             kollisjoner: 0,
+            gravsteiner: 0,
+            maks_lasttall: Self::STANDARD_MAKS_LASTTALL,
+            inkrementell: false,
+            omhashinger: 0,
+            gammel: Vec::new(),
+            flyttet: 0,
+            probing: Probing::Dobbel,
         }
     }
 
-    // Obs, antar her at kapasiteten til tabellen er et primtall
-    fn hash(&self, nøkkel: usize) -> usize {
-        nøkkel % self.kapasitet
+    pub fn med_probing(mut self, probing: Probing) -> Self {
+        self.probing = probing;
+        self
     }
 
-    fn hash2(&self, nøkkel: usize) -> usize {
-        (nøkkel % (self.kapasitet - 1)) + 1
+    pub fn med_maks_lasttall(mut self, maks_lasttall: f64) -> Self {
+        assert!(
+            maks_lasttall > 0.0 && maks_lasttall <= 1.0,
+            "Maksimalt lasttall må være større enn 0 og høyst 1"
+        );
+        self.maks_lasttall = maks_lasttall;
+        self
     }
 
-    pub fn sett_inn(&mut self, nøkkel: usize) -> () {
-        // Beregn hash
-        let mut hash = self.hash(nøkkel);
+    /// Flytt innholdet over til en utvidet tabell litt av gangen, så ingen enkelt innsetting må vente på
+    /// at hele tabellen hashes om
+    pub fn med_inkrementell_omhashing(mut self, inkrementell: bool) -> Self {
+        self.inkrementell = inkrementell;
+        self
+    }
+
+    /// Hasher om tabellen om en ny nøkkel ville gitt for høyt lasttall. Gravsteiner teller med her, siden
+    /// probing må gå forbi dem like mye som forbi nøkler. Er det gravsteinene som fyller opp tabellen beholdes
+    /// kapasiteten, og omhashingen rydder bare bort gravsteinene. Ellers utvides tabellen til minst dobbel kapasitet
+    fn voks_ved_behov(&mut self) {
+        let grense = self.maks_lasttall * self.kapasitet as f64;
+        if (self.antall_verdier + self.gravsteiner + 1) as f64 <= grense {
+            return;
+        }
+
+        // En tidligere omhashing må være ferdig flyttet før neste kan begynne
+        self.flytt_over(self.gammel.len());
+
+        if (self.antall_verdier + 1) as f64 > grense / 2.0 {
+            self.kapasitet = Self::neste_kapasitet(2 * self.kapasitet);
+        }
+        self.gammel = mem::replace(&mut self.tabell, vec![Plass::Ledig; self.kapasitet]);
+        self.gravsteiner = 0;
+        self.omhashinger += 1;
+
+        if !self.inkrementell {
+            self.flytt_over(self.gammel.len());
+        }
+    }
+
+    /// Flytter inntil `antall` plasser fra den gamle tabellen over i den nye. Gravsteiner blir liggende igjen,
+    /// og plassene som er flyttet blir selv gravsteiner, så oppslag i den gamle tabellen fortsatt leter forbi dem
+    fn flytt_over(&mut self, antall: usize) {
+        let slutt = usize::min(self.flyttet + antall, self.gammel.len());
+        for indeks in self.flyttet..slutt {
+            if let Plass::Opptatt(nøkkel) = mem::replace(&mut self.gammel[indeks], Plass::Slettet)
+            {
+                self.plasser(nøkkel);
+            }
+        }
+        self.flyttet = slutt;
+
+        if self.flyttet == self.gammel.len() {
+            self.gammel = Vec::new();
+            self.flyttet = 0;
+        }
+    }
+
+    // Kapasiteten er alltid et primtall, se `neste_kapasitet`
+    fn hash(nøkkel: usize, kapasitet: usize) -> usize {
+        nøkkel % kapasitet
+    }
+
+    fn hash2(nøkkel: usize, kapasitet: usize) -> usize {
+        (nøkkel % (kapasitet - 1)) + 1
+    }
+
+    /// Setter inn nøkkelen om den ikke finnes fra før, og gir om den ble satt inn
+    pub fn sett_inn(&mut self, nøkkel: usize) -> bool {
+        // Tabellen er en mengde, så en nøkkel som allerede finnes settes ikke inn på nytt
+        if self.inneholder(nøkkel) {
+            return false;
+        }
+
+        // Under inkrementell omhashing kommer flyttingen et steg videre for hver innsetting
+        if !self.gammel.is_empty() {
+            self.flytt_over(PLASSER_PER_STEG);
+        }
+
+        // Sørg for at det finnes en ledig plass før vi leter etter den
+        self.voks_ved_behov();
+
+        self.kollisjoner += self.plasser(nøkkel);
+        self.antall_verdier += 1;
+
+        true
+    }
+
+    pub fn inneholder(&self, nøkkel: usize) -> bool {
+        Self::finn_i(&self.tabell, self.probing, nøkkel).is_some()
+            || Self::finn_i(&self.gammel, self.probing, nøkkel).is_some()
+    }
+
+    /// Fjerner nøkkelen, og gir om den fantes. Ved lineær probing og Robin Hood flyttes nøklene bak
+    /// bakover i stedet for å legge igjen en gravstein, så tabellen blir som om nøkkelen aldri var satt inn
+    pub fn fjern(&mut self, nøkkel: usize) -> bool {
+        if let Some((plass, _)) = Self::finn_i(&self.tabell, self.probing, nøkkel) {
+            match self.probing {
+                Probing::Lineær | Probing::RobinHood => self.flytt_bakover(plass),
+                Probing::Kvadratisk | Probing::Dobbel => {
+                    self.tabell[plass] = Plass::Slettet;
+                    self.gravsteiner += 1;
+                }
+            }
+        } else if let Some((plass, _)) = Self::finn_i(&self.gammel, self.probing, nøkkel) {
+            // Den gamle tabellen forsvinner når flyttingen er ferdig, så der holder det med en gravstein
+            self.gammel[plass] = Plass::Slettet;
+        } else {
+            return false;
+        }
+
+        self.antall_verdier -= 1;
+        true
+    }
+
+    /// Tetter hullet etter en fjernet nøkkel ved lineær probing: går gjennom resten av klyngen bak hullet,
+    /// og flytter en nøkkel inn i hullet når startplassen dens ikke ligger mellom hullet og der den står.
+    /// Da kan den ikke finnes uten å gå forbi hullet, og plassen den forlater blir det nye hullet
+    fn flytt_bakover(&mut self, plass: usize) {
+        // Hullet holdes ledig underveis, så gjennomgangen stopper selv om tabellen var helt full
+        let mut hull = plass;
+        let mut neste = plass;
+        self.tabell[hull] = Plass::Ledig;
+
+        loop {
+            neste = (neste + 1) % self.kapasitet;
+
+            let nøkkel = match self.tabell[neste] {
+                Plass::Opptatt(nøkkel) => nøkkel,
+                _ => break,
+            };
+
+            let start = Self::hash(nøkkel, self.kapasitet);
+            let mellom = if hull <= neste {
+                hull < start && start <= neste
+            } else {
+                hull < start || start <= neste
+            };
+
+            if !mellom {
+                self.tabell[hull] = Plass::Opptatt(nøkkel);
+                self.tabell[neste] = Plass::Ledig;
+                hull = neste;
+            }
+        }
+    }
+
+    /// Plassene nøkkelen prøver i en tabell med angitt kapasitet, i rekkefølge
+    fn probesekvens(probing: Probing, nøkkel: usize, kapasitet: usize) -> Probesekvens {
+        Probesekvens::new(
+            probing,
+            Self::hash(nøkkel, kapasitet),
+            Self::hash2(nøkkel, kapasitet),
+            kapasitet,
+        )
+    }
+
+    /// Hvor langt fra startplassen sin nøkkelen på angitt plass står, ved lineær probing
+    fn avstand(plass: usize, nøkkel: usize, kapasitet: usize) -> usize {
+        (plass + kapasitet - Self::hash(nøkkel, kapasitet)) % kapasitet
+    }
+
+    /// Setter nøkkelen på første ledige plass den kan havne på, og gir antall kollisjoner underveis
+    fn plasser(&mut self, nøkkel: usize) -> usize {
+        if self.probing == Probing::RobinHood {
+            return self.plasser_robin_hood(nøkkel);
+        }
+
+        // Gå gjennom plassene nøkkelen kan havne på til vi finner en ledig, eller en gravstein vi kan
+        // bruke på nytt. Tabellen er aldri full, og alle probingene kommer innom alle plassene,
+        // så en plass blir alltid funnet
+        let mut kollisjoner = 0;
+        for plass in Self::probesekvens(self.probing, nøkkel, self.kapasitet) {
+            match self.tabell[plass] {
+                Plass::Ledig => {}
+                Plass::Slettet => self.gravsteiner -= 1,
+                Plass::Opptatt(_) => {
+                    kollisjoner += 1;
+                    continue;
+                }
+            }
+
+            self.tabell[plass] = Plass::Opptatt(nøkkel);
+            break;
+        }
+
+        kollisjoner
+    }
+
+    /// Lineær probing, der nøkkelen som settes inn tar over plassen til en nøkkel som står nærmere
+    /// startplassen sin. Den fortrengte nøkkelen leter så videre fra samme sted
+    fn plasser_robin_hood(&mut self, nøkkel: usize) -> usize {
+        let mut kollisjoner = 0;
+        let mut nøkkel = nøkkel;
+        let mut avstand = 0;
+        let mut plass = Self::hash(nøkkel, self.kapasitet);
+
+        while let Plass::Opptatt(annen) = self.tabell[plass] {
+            kollisjoner += 1;
+
+            let annen_avstand = Self::avstand(plass, annen, self.kapasitet);
+            if annen_avstand < avstand {
+                self.tabell[plass] = Plass::Opptatt(nøkkel);
+                nøkkel = annen;
+                avstand = annen_avstand;
+            }
+
+            plass = (plass + 1) % self.kapasitet;
+            avstand += 1;
+        }
+
+        self.tabell[plass] = Plass::Opptatt(nøkkel);
+
+        kollisjoner
+    }
+
+    /// Plassen nøkkelen står på i tabellen, og hvor mange plasser et oppslag må innom for å finne den
+    fn finn_i(tabell: &[Plass], probing: Probing, nøkkel: usize) -> Option<(usize, usize)> {
+        let kapasitet = tabell.len();
+        if kapasitet == 0 {
+            return None;
+        }
+
+        let sekvens = Self::probesekvens(probing, nøkkel, kapasitet).take(kapasitet);
+        for (forsøk, plass) in sekvens.enumerate() {
+            match tabell[plass] {
+                Plass::Opptatt(annen) if annen == nøkkel => return Some((plass, forsøk + 1)),
+                // Ved Robin Hood står nøklene sortert etter avstand, så en nøkkel nærmere startplassen sin
+                // enn vi har kommet betyr at nøkkelen ikke finnes lenger ut heller
+                Plass::Opptatt(annen)
+                    if probing == Probing::RobinHood
+                        && Self::avstand(plass, annen, kapasitet) < forsøk =>
+                {
+                    return None
+                }
+                Plass::Opptatt(_) | Plass::Slettet => {}
+                Plass::Ledig => return None,
+            }
+        }
 
-        // Hvis en kollisjon inntreffer (noen andre er allerede på den ønskede plassen)
-        if let Some(_) = self.tabell[hash] {
-            // Regn ut sekundær hash
-            let hash2 = self.hash2(nøkkel);
+        None
+    }
 
-            loop {
-                self.kollisjoner = self.kollisjoner + 1;
-                // Hopp fremover i tabellen med hopplengde hash2
-                hash = (hash + hash2) % self.kapasitet;
+    /// Probelengden til hver nøkkel i tabellen, altså hvor mange plasser et oppslag må innom for å finne den.
+    /// Nøkler som ennå ligger i en gammel tabell under inkrementell omhashing er ikke med
+    pub fn probestatistikk(&self) -> Probestatistikk {
+        let mut statistikk = Probestatistikk::default();
 
-                // Hvis vi hoppet til en ledig plass, bryt løkken
-                if let None = self.tabell[hash] {
-                    break;
+        for plass in &self.tabell {
+            if let Plass::Opptatt(nøkkel) = plass {
+                if let Some((_, probelengde)) = Self::finn_i(&self.tabell, self.probing, *nøkkel) {
+                    statistikk.legg_til(probelengde);
                 }
             }
         }
 
-        // Etter rutinen ovenfor er vi garantert å ha funnet en ledig plass, og setter inn den nye verdien
-        self.tabell[hash] = Some(nøkkel);
-        self.antall_verdier = self.antall_verdier + 1;
+        statistikk
+    }
+}
+
+/// Lager en minimal perfekt hashfunksjon for navnene, lagrer den i fil og leser den inn igjen,
+/// og sjekker at alle navnene får hver sin plass med funksjonen som ble lest inn
+fn perfekt_hashing(navneliste: Vec<String>, fil: &str) -> Result<(), String> {
+    let start = Instant::now();
+    let funksjon = PerfektHash::bygg(&navneliste)?;
+    let tid = Instant::now() - start;
+
+    println!(
+        "Minimal perfekt hashfunksjon for {} navn laget på {} sekunder",
+        navneliste.len(),
+        tid.as_secs_f32()
+    );
+
+    funksjon.lagre(fil)?;
+    let funksjon = PerfektHash::les(fil)?;
+    let størrelse = fs::metadata(fil).map(|data| data.len()).unwrap_or(0);
+
+    println!("Lagret i {fil} ({størrelse} byte) og lest inn igjen");
+    println!("{:.2} bit per navn", funksjon.bit_per_nøkkel());
+
+    let tabell = PerfektTabell::new(funksjon, navneliste)?;
+
+    println!("Alle navnene fikk hver sin plass, uten kollisjoner");
+    match tabell.plass("Jakob Karevold Grønhaug") {
+        Some(plass) => println!("Jeg (Jakob Karevold Grønhaug) har plass {plass}"),
+        None => println!("Jeg (Jakob Karevold Grønhaug) er ikke blant navnene"),
     }
+
+    Ok(())
 }
 
 fn main() {
-    let args: Vec<String> = args().collect();
+    let mut args: Vec<String> = args().collect();
+
+    let hjelp = format!(
+        "Forventet bruk: {} [valg] [navnefil]
+Valg:
+\t--maks-lasttall-1 x     Største lasttall før tabellen i oppgave 1 utvides (standard {})
+\t--maks-lasttall-2 x     Største lasttall før tabellen i oppgave 2 utvides, høyst 1 (standard {})
+\t--maks-lasttall-gjøk x  Største lasttall før gjøktabellen utvides, under 1 (standard {})
+\t--inkrementell          Flytt innholdet over til utvidede tabeller litt av gangen
+\t--kollisjoner k         Kollisjoner i oppgave 1: vis (standard), plass (vis med plassen i tabellen) eller skjul (vis bare antallet)
+\t--probing p             Probing i oppgave 2: dobbel (standard), lineær, kvadratisk, robin-hood eller alle
+\t--sammenlign-hash       Sammenlign hvor jevnt ulike hashfunksjoner fordeler navnene, i stedet for oppgavene
+\t--perfekt fil           Lag en minimal perfekt hashfunksjon for navnene og lagre den i fil, i stedet for oppgavene",
+        args[0],
+        HashTabell::<String, usize>::STANDARD_MAKS_LASTTALL,
+        HashTabell2::STANDARD_MAKS_LASTTALL,
+        GjøkTabell::STANDARD_MAKS_LASTTALL
+    );
+
+    // Plukk ut valgfrie grenser for når tabellene skal utvides
+    let mut maks_lasttall_1 = HashTabell::<String, usize>::STANDARD_MAKS_LASTTALL;
+    if let Some(indeks) = args.iter().position(|arg| arg == "--maks-lasttall-1") {
+        maks_lasttall_1 = match args.get(indeks + 1).map(|lasttall| lasttall.parse()) {
+            Some(Ok(lasttall)) if lasttall > 0.0 => lasttall,
+            _ => {
+                println!("Ugyldig lasttall for oppgave 1");
+                println!("{}", hjelp);
+                exit(1);
+            }
+        };
+        args.drain(indeks..indeks + 2);
+    }
+
+    let mut maks_lasttall_2 = HashTabell2::STANDARD_MAKS_LASTTALL;
+    if let Some(indeks) = args.iter().position(|arg| arg == "--maks-lasttall-2") {
+        maks_lasttall_2 = match args.get(indeks + 1).map(|lasttall| lasttall.parse()) {
+            Some(Ok(lasttall)) if lasttall > 0.0 && lasttall <= 1.0 => lasttall,
+            _ => {
+                println!("Ugyldig lasttall for oppgave 2");
+                println!("{}", hjelp);
+                exit(1);
+            }
+        };
+        args.drain(indeks..indeks + 2);
+    }
+
+    let mut maks_lasttall_gjøk = GjøkTabell::STANDARD_MAKS_LASTTALL;
+    if let Some(indeks) = args.iter().position(|arg| arg == "--maks-lasttall-gjøk") {
+        maks_lasttall_gjøk = match args.get(indeks + 1).map(|lasttall| lasttall.parse()) {
+            Some(Ok(lasttall)) if lasttall > 0.0 && lasttall < 1.0 => lasttall,
+            _ => {
+                println!("Ugyldig lasttall for gjøktabellen");
+                println!("{}", hjelp);
+                exit(1);
+            }
+        };
+        args.drain(indeks..indeks + 2);
+    }
+
+    let inkrementell = match args.iter().position(|arg| arg == "--inkrementell") {
+        Some(indeks) => {
+            args.remove(indeks);
+            true
+        }
+        None => false,
+    };
+
+    let mut kollisjoner = "vis".to_string();
+    if let Some(indeks) = args.iter().position(|arg| arg == "--kollisjoner") {
+        kollisjoner = match args.get(indeks + 1).map(String::as_str) {
+            Some(valg @ ("vis" | "plass" | "skjul")) => valg.to_string(),
+            _ => {
+                println!("Ugyldig valg for kollisjoner");
+                println!("{}", hjelp);
+                exit(1);
+            }
+        };
+        args.drain(indeks..indeks + 2);
+    }
+
+    let mut probinger = vec![Probing::Dobbel];
+    if let Some(indeks) = args.iter().position(|arg| arg == "--probing") {
+        probinger = match args.get(indeks + 1).map(String::as_str) {
+            Some("alle") => Probing::ALLE.to_vec(),
+            Some(navn) if Probing::fra_navn(navn).is_some() => {
+                vec![Probing::fra_navn(navn).unwrap()]
+            }
+            _ => {
+                println!("Ugyldig probing");
+                println!("{}", hjelp);
+                exit(1);
+            }
+        };
+        args.drain(indeks..indeks + 2);
+    }
+
+    let mut perfekt_fil = None;
+    if let Some(indeks) = args.iter().position(|arg| arg == "--perfekt") {
+        perfekt_fil = match args.get(indeks + 1) {
+            Some(fil) => Some(fil.clone()),
+            None => {
+                println!("Mangler fil for den perfekte hashfunksjonen");
+                println!("{}", hjelp);
+                exit(1);
+            }
+        };
+        args.drain(indeks..indeks + 2);
+    }
+
+    let sammenlign_hash = match args.iter().position(|arg| arg == "--sammenlign-hash") {
+        Some(indeks) => {
+            args.remove(indeks);
+            true
+        }
+        None => false,
+    };
 
     let filnavn: String = match args.get(1) {
         Some(arg) => arg.clone(),
@@ -208,21 +1008,63 @@ fn main() {
         Err(_) => exit(1),
     };
 
+    if sammenlign_hash {
+        println!("{}", hashfunksjoner::sammenligning(&navneliste, 127));
+        return;
+    }
+
+    if let Some(fil) = perfekt_fil {
+        if let Err(melding) = perfekt_hashing(navneliste, &fil) {
+            println!("{melding}");
+            exit(1);
+        }
+        return;
+    }
+
     println!("Oppgave 1:");
 
-    let mut hashtabell: HashTabell = HashTabell::new(127);
+    // Hvert navn får linjenummeret sitt i navnefilen som verdi. Står et navn flere ganger beholdes
+    // linjenummeret der det sto først
+    let mut hashtabell: HashTabell<String, usize> = HashTabell::new(127)
+        .med_maks_lasttall(maks_lasttall_1)
+        .med_inkrementell_omhashing(inkrementell);
+
+    // Med plass samles kollisjonene i en logg og skrives ut når alle navnene er satt inn, og med skjul
+    // telles de bare
+    let mut utskrift = Utskrift;
+    let mut logg = Logg::default();
+    let mut teller = Teller::default();
+    let observatør: &mut dyn Kollisjonsobservatør<String> = match kollisjoner.as_str() {
+        "plass" => &mut logg,
+        "skjul" => &mut teller,
+        _ => &mut utskrift,
+    };
+
+    for (linjenummer, navn) in navneliste.iter().enumerate() {
+        if let Some(første) = hashtabell.sett_inn(navn.clone(), linjenummer + 1, &mut *observatør)
+        {
+            *hashtabell.get_mut(navn).unwrap() = første;
+        }
+    }
 
-    for navn in navneliste {
-        hashtabell.sett_inn(navn);
+    for (navn, plass, andre) in &logg.kollisjoner {
+        println!("{plass:>4}: {navn} kolliderte med {}", andre.join(", "));
+    }
+    if teller.antall > 0 {
+        println!("{} kollisjoner skjult", teller.antall);
     }
 
     println!();
 
     println!(
         "Jeg (Jakob Karevold Grønhaug) er i hashtabellen: {}",
-        hashtabell.inneholder("Jakob Karevold Grønhaug".to_string())
+        hashtabell.inneholder("Jakob Karevold Grønhaug")
     );
 
+    if let Some(linjenummer) = hashtabell.get("Jakob Karevold Grønhaug") {
+        println!("(på linje {linjenummer} i {filnavn})");
+    }
+
     println!(
         "{} kollisjoner på {} innsettinger",
         hashtabell.kollisjoner, hashtabell.antall_verdier
@@ -232,48 +1074,516 @@ fn main() {
         hashtabell.kollisjoner as f32 / hashtabell.antall_verdier as f32
     );
     println!("Lasttall: {}", hashtabell.lasttall());
+    println!("Omhashinger: {}", hashtabell.omhashinger);
+
+    // Fjern annethvert navn og sett det inn igjen, og sjekk at alle navnene står med linjenummeret sitt
+    let fjernet: Vec<(String, usize)> = navneliste
+        .iter()
+        .step_by(2)
+        .filter_map(|navn| {
+            hashtabell
+                .remove(navn)
+                .map(|linjenummer| (navn.clone(), linjenummer))
+        })
+        .collect();
+    let antall_fjernet = fjernet.len();
+    for (navn, linjenummer) in fjernet {
+        hashtabell.insert(navn, linjenummer);
+    }
+    let riktige = hashtabell
+        .iter()
+        .filter(|&(navn, &linjenummer)| navneliste[linjenummer - 1] == *navn)
+        .count();
+    println!(
+        "Fjernet {antall_fjernet} navn og satte dem inn igjen, og {riktige} av {} har riktig linjenummer",
+        hashtabell.len()
+    );
 
     drop(hashtabell);
 
     println!("\nOppgave 2:");
 
-    let mut hashtabell: HashTabell2 = HashTabell2::new(13_000_027); // Neste primtall etter 13 000 000
-
     // Ti millioner tall med største mulige spredning for 64 bits
     let tall_liste = tilfeldige_heltall(10_000_000, usize::MAX);
 
-    let mut start: Instant = Instant::now();
+    for probing in probinger {
+        let mut hashtabell: HashTabell2 = HashTabell2::new(13_000_027) // Neste primtall etter 13 000 000
+            .med_probing(probing)
+            .med_maks_lasttall(maks_lasttall_2)
+            .med_inkrementell_omhashing(inkrementell);
+
+        println!("\nProbing: {}", probing.navn());
+
+        let start: Instant = Instant::now();
+        for tall in &tall_liste {
+            hashtabell.sett_inn(*tall);
+        }
+
+        let tid: Duration = Instant::now() - start;
+
+        println!(
+            "Satte inn på {} sekunder, med {} kollisjoner",
+            tid.as_secs_f32(),
+            hashtabell.kollisjoner
+        );
+        println!(
+            "({} kollisjoner per innsetting)",
+            hashtabell.kollisjoner as f64 / hashtabell.antall_verdier as f64
+        );
+        println!(
+            "Lasttall: {}",
+            hashtabell.antall_verdier as f64 / hashtabell.kapasitet as f64
+        );
+        println!("Omhashinger: {}", hashtabell.omhashinger);
+
+        let statistikk = hashtabell.probestatistikk();
+        println!(
+            "Probelengde ved oppslag: {} i snitt, {} på det lengste",
+            statistikk.gjennomsnitt(),
+            statistikk.lengste()
+        );
+        print!("{statistikk}");
+
+        let start = Instant::now();
+        let funnet = tall_liste
+            .iter()
+            .filter(|&&tall| hashtabell.inneholder(tall))
+            .count();
+        let tid = Instant::now() - start;
+        println!(
+            "Fant {funnet} av {} igjen på {} sekunder",
+            tall_liste.len(),
+            tid.as_secs_f32()
+        );
+
+        let start = Instant::now();
+        for tall in tall_liste.iter().step_by(2) {
+            hashtabell.fjern(*tall);
+        }
+        let tid = Instant::now() - start;
+        println!(
+            "Fjernet annethvert tall på {} sekunder, og {} gravsteiner ble liggende igjen",
+            tid.as_secs_f32(),
+            hashtabell.gravsteiner
+        );
+    }
+
+    // Samme innsettinger med gjøkhashing, som aldri trenger mer enn to plasser for et oppslag
+    println!("\nGjøkhashing:");
+
+    let mut gjøktabell = GjøkTabell::new(13_000_027).med_maks_lasttall(maks_lasttall_gjøk);
+
+    let start = Instant::now();
     for tall in &tall_liste {
-        hashtabell.sett_inn(*tall);
+        gjøktabell.sett_inn(*tall);
     }
 
-    let mut tid: Duration = Instant::now() - start;
+    let tid = Instant::now() - start;
 
     println!(
-        "Satte inn på {} sekunder, med {} kollisjoner",
+        "Satte inn på {} sekunder, med {} forflytninger",
         tid.as_secs_f32(),
-        hashtabell.kollisjoner
+        gjøktabell.forflytninger
+    );
+    println!(
+        "({} forflytninger per innsetting)",
+        gjøktabell.forflytninger as f64 / gjøktabell.len() as f64
     );
+    println!("Lasttall: {}", gjøktabell.lasttall());
     println!(
-        "({} kollisjoner per innsetting)",
-        hashtabell.kollisjoner as f64 / hashtabell.antall_verdier as f64
+        "Omhashinger: {}, hvorav {} utvidelser",
+        gjøktabell.omhashinger, gjøktabell.utvidelser
     );
+
+    let start = Instant::now();
+    let funnet = tall_liste
+        .iter()
+        .filter(|&&tall| gjøktabell.inneholder(tall))
+        .count();
+    let tid = Instant::now() - start;
     println!(
-        "Lasttall: {}",
-        hashtabell.antall_verdier as f64 / hashtabell.kapasitet as f64
+        "Fant {funnet} av {} igjen på {} sekunder",
+        tall_liste.len(),
+        tid.as_secs_f32()
     );
 
+    let start = Instant::now();
+    for tall in tall_liste.iter().step_by(2) {
+        gjøktabell.fjern(*tall);
+    }
+    let tid = Instant::now() - start;
+    println!("Fjernet annethvert tall på {} sekunder", tid.as_secs_f32());
+
+    drop(gjøktabell);
+
     let mut innebygd: HashMap<usize, usize> = HashMap::new();
 
-    start = Instant::now();
+    let start = Instant::now();
     for tall in &tall_liste {
         innebygd.insert(*tall, *tall);
     }
 
-    tid = Instant::now() - start;
+    let tid = Instant::now() - start;
+
+    println!(
+        "\nInnebygd hashtabell gjorde samme innsettinger på {} sekunder",
+        tid.as_secs_f32()
+    );
 
+    let start = Instant::now();
+    let funnet = tall_liste
+        .iter()
+        .filter(|tall| innebygd.contains_key(tall))
+        .count();
+    let tid = Instant::now() - start;
     println!(
-        "Innebygd hashtabell gjorde samme innsettinger på {} sekunder",
+        "og fant {funnet} av {} igjen på {} sekunder",
+        tall_liste.len(),
         tid.as_secs_f32()
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// En tabell der omhashing til større kapasitet pågår, med nøklene 0 til og med 31 og verdien 10 * nøkkel
+    fn under_omhashing() -> HashTabell<usize, usize> {
+        let mut tabell = HashTabell::new(31).med_inkrementell_omhashing(true);
+        for nøkkel in 0..32 {
+            tabell.insert(nøkkel, 10 * nøkkel);
+        }
+
+        assert_eq!(tabell.omhashinger, 1);
+        assert!(!tabell.gammel.is_empty());
+        tabell
+    }
+
+    #[test]
+    fn insert_gir_gammel_verdi() {
+        let mut tabell = HashTabell::new(7);
+
+        assert_eq!(tabell.insert("a".to_string(), 1), None);
+        assert_eq!(tabell.insert("a".to_string(), 2), Some(1));
+        assert_eq!(tabell.get("a"), Some(&2));
+        assert_eq!(tabell.len(), 1);
+    }
+
+    #[test]
+    fn kapasitet_0_blir_1() {
+        let mut tabell = HashTabell::new(0);
+        assert_eq!(tabell.kapasitet, 1);
+        assert_eq!(tabell.get("a"), None);
+        assert_eq!(tabell.remove("a"), None);
+
+        for nøkkel in ["a", "b", "c"] {
+            tabell.insert(nøkkel.to_string(), nøkkel.len());
+        }
+        assert_eq!(tabell.get("b"), Some(&1));
+        assert_eq!(tabell.len(), 3);
+    }
+
+    #[test]
+    fn remove_gir_verdien_og_fjerner_nøkkelen() {
+        let mut tabell = HashTabell::new(3);
+        for nøkkel in 0..10 {
+            tabell.insert(nøkkel, nøkkel + 100);
+        }
+
+        assert_eq!(tabell.remove(&4), Some(104));
+        assert_eq!(tabell.remove(&4), None);
+        assert_eq!(tabell.get(&4), None);
+        assert!(!tabell.inneholder(&4));
+        assert_eq!(tabell.len(), 9);
+
+        for nøkkel in (0..10).filter(|&nøkkel| nøkkel != 4) {
+            assert_eq!(tabell.get(&nøkkel), Some(&(nøkkel + 100)));
+        }
+    }
+
+    #[test]
+    fn get_finner_nøkler_i_gammel_tabell_under_omhashing() {
+        let tabell = under_omhashing();
+
+        for nøkkel in 0..32 {
+            assert_eq!(tabell.get(&nøkkel), Some(&(10 * nøkkel)));
+        }
+        assert_eq!(tabell.get(&32), None);
+        assert_eq!(tabell.iter().count(), 32);
+    }
+
+    #[test]
+    fn get_mut_under_omhashing() {
+        let mut tabell = under_omhashing();
+
+        *tabell.get_mut(&5).unwrap() += 1;
+        assert!(!tabell.gammel.is_empty());
+        assert_eq!(tabell.get_mut(&32), None);
+
+        for nøkkel in 0..32 {
+            *tabell.get_mut(&nøkkel).unwrap() += 1;
+        }
+        assert!(tabell.gammel.is_empty());
+
+        assert_eq!(tabell.get(&5), Some(&52));
+        for nøkkel in (0..32).filter(|&nøkkel| nøkkel != 5) {
+            assert_eq!(tabell.get(&nøkkel), Some(&(10 * nøkkel + 1)));
+        }
+        assert_eq!(tabell.len(), 32);
+    }
+
+    #[test]
+    fn remove_under_omhashing() {
+        let mut tabell = under_omhashing();
+
+        assert_eq!(tabell.remove(&7), Some(70));
+        assert_eq!(tabell.remove(&7), None);
+        assert_eq!(tabell.get(&7), None);
+        assert_eq!(tabell.len(), 31);
+    }
+
+    #[test]
+    fn entry_or_insert_og_and_modify() {
+        let mut tabell: HashTabell<&str, usize> = HashTabell::new(3);
+
+        for ord in ["a", "b", "a", "c", "a", "b"] {
+            tabell
+                .entry(ord)
+                .and_modify(|antall| *antall += 1)
+                .or_insert(1);
+        }
+
+        assert_eq!(tabell.get("a"), Some(&3));
+        assert_eq!(tabell.get("b"), Some(&2));
+        assert_eq!(tabell.get("c"), Some(&1));
+
+        // or_insert lar en verdi som finnes fra før være
+        *tabell.entry("c").or_insert(100) += 1;
+        assert_eq!(tabell.get("c"), Some(&2));
+        assert_eq!(tabell.len(), 3);
+    }
+
+    /// En tom HashTabell2 for hver probing, med og uten inkrementell omhashing
+    fn tabeller2(kapasitet: usize) -> Vec<HashTabell2> {
+        Probing::ALLE
+            .iter()
+            .flat_map(|&probing| {
+                [false, true].map(|inkrementell| {
+                    HashTabell2::new(kapasitet)
+                        .med_probing(probing)
+                        .med_inkrementell_omhashing(inkrementell)
+                })
+            })
+            .collect()
+    }
+
+    /// Nøkler spredt utover, der mange har samme startplass i tabeller med kapasitet 103
+    fn nøkler(antall: usize) -> Vec<usize> {
+        (0..antall).map(|i| (i % 10) * 103 + i / 10 * 7).collect()
+    }
+
+    /// Ved Robin Hood står nøklene i hver klynge sortert etter startplass, så ingen nøkkel står mer
+    /// enn én plass lenger fra startplassen sin enn nøkkelen foran
+    fn robin_hood_ordnet(tabell: &HashTabell2) -> bool {
+        (0..tabell.kapasitet).all(|plass| {
+            let neste = (plass + 1) % tabell.kapasitet;
+            match (tabell.tabell[plass], tabell.tabell[neste]) {
+                (Plass::Opptatt(nøkkel), Plass::Opptatt(annen)) => {
+                    HashTabell2::avstand(neste, annen, tabell.kapasitet)
+                        <= HashTabell2::avstand(plass, nøkkel, tabell.kapasitet) + 1
+                }
+                (_, Plass::Opptatt(annen)) => {
+                    HashTabell2::avstand(neste, annen, tabell.kapasitet) == 0
+                }
+                _ => true,
+            }
+        })
+    }
+
+    #[test]
+    fn probing_sett_inn_og_finn() {
+        for mut tabell in tabeller2(103) {
+            let navn = format!("{} {}", tabell.probing.navn(), tabell.inkrementell);
+
+            for &nøkkel in &nøkler(300) {
+                assert!(tabell.sett_inn(nøkkel), "{navn}");
+            }
+
+            assert_eq!(tabell.antall_verdier, 300, "{navn}");
+            assert!(tabell.omhashinger >= 1, "{navn}");
+            assert!(
+                nøkler(300).iter().all(|&nøkkel| tabell.inneholder(nøkkel)),
+                "{navn}"
+            );
+            assert!(!tabell.inneholder(1), "{navn}");
+            assert!(!tabell.inneholder(10_000), "{navn}");
+        }
+    }
+
+    #[test]
+    fn probestatistikk_for_hver_probing() {
+        for mut tabell in tabeller2(103) {
+            for &nøkkel in &nøkler(90) {
+                tabell.sett_inn(nøkkel);
+            }
+
+            let statistikk = tabell.probestatistikk();
+            assert!(statistikk.lengste() >= 1);
+            assert!(statistikk.gjennomsnitt() >= 1.0);
+            assert!(statistikk.gjennomsnitt() <= statistikk.lengste() as f64);
+        }
+    }
+
+    #[test]
+    fn robin_hood_holder_klyngene_sortert() {
+        let mut tabell = HashTabell2::new(103).med_probing(Probing::RobinHood);
+
+        // Alle nøklene med samme startplass først, så de andre må skyve dem foran seg
+        for &nøkkel in nøkler(90).iter().rev() {
+            tabell.sett_inn(nøkkel);
+            assert!(robin_hood_ordnet(&tabell));
+        }
+        assert!(nøkler(90).iter().all(|&nøkkel| tabell.inneholder(nøkkel)));
+    }
+
+    #[test]
+    fn duplikater_settes_ikke_inn() {
+        for mut tabell in tabeller2(103) {
+            for &nøkkel in &nøkler(100) {
+                assert!(tabell.sett_inn(nøkkel));
+            }
+            for &nøkkel in &nøkler(100) {
+                assert!(!tabell.sett_inn(nøkkel));
+            }
+
+            assert_eq!(tabell.antall_verdier, 100);
+        }
+    }
+
+    #[test]
+    fn fjern_for_hver_probing() {
+        for mut tabell in tabeller2(103) {
+            let navn = format!("{} {}", tabell.probing.navn(), tabell.inkrementell);
+            let nøkler = nøkler(300);
+            for &nøkkel in &nøkler {
+                tabell.sett_inn(nøkkel);
+            }
+
+            for &nøkkel in nøkler.iter().step_by(2) {
+                assert!(tabell.fjern(nøkkel), "{navn}");
+                assert!(!tabell.fjern(nøkkel), "{navn}");
+            }
+
+            assert_eq!(tabell.antall_verdier, 150, "{navn}");
+            for (i, &nøkkel) in nøkler.iter().enumerate() {
+                assert_eq!(tabell.inneholder(nøkkel), i % 2 == 1, "{navn}");
+            }
+
+            // Lineær probing og Robin Hood tetter hullene i stedet for å legge igjen gravsteiner
+            let gravsteiner = tabell
+                .tabell
+                .iter()
+                .filter(|&&plass| plass == Plass::Slettet);
+            match tabell.probing {
+                Probing::Lineær | Probing::RobinHood => assert_eq!(gravsteiner.count(), 0),
+                Probing::Kvadratisk | Probing::Dobbel => {
+                    assert_eq!(gravsteiner.count(), tabell.gravsteiner, "{navn}");
+                    assert!(tabell.gravsteiner > 0, "{navn}");
+                }
+            }
+
+            // Plassene kan brukes på nytt
+            for &nøkkel in nøkler.iter().step_by(2) {
+                assert!(tabell.sett_inn(nøkkel), "{navn}");
+            }
+            assert_eq!(tabell.antall_verdier, 300, "{navn}");
+            assert!(
+                nøkler.iter().all(|&nøkkel| tabell.inneholder(nøkkel)),
+                "{navn}"
+            );
+        }
+    }
+
+    /// Fjerner nøklene én og én i angitt rekkefølge, og sjekker at resten fortsatt finnes etter hver gang
+    fn fjern_én_og_én(probing: Probing, rekkefølge: &[usize]) {
+        let mut tabell = HashTabell2::new(103).med_probing(probing);
+        for &nøkkel in &nøkler(90) {
+            tabell.sett_inn(nøkkel);
+        }
+
+        for (fjernet, &nøkkel) in rekkefølge.iter().enumerate() {
+            assert!(tabell.fjern(nøkkel));
+            assert!(!tabell.tabell.contains(&Plass::Slettet));
+            if probing == Probing::RobinHood {
+                assert!(robin_hood_ordnet(&tabell));
+            }
+
+            // Ved Robin Hood stopper oppslag tidlig, så en nøkkel flyttet feil ville ikke blitt funnet
+            assert!(rekkefølge[fjernet + 1..]
+                .iter()
+                .all(|&annen| tabell.inneholder(annen)));
+            assert!(!tabell.inneholder(nøkkel));
+        }
+    }
+
+    #[test]
+    fn flytt_bakover_holder_klyngene_sammen() {
+        let mut rekkefølge = nøkler(90);
+        for probing in [Probing::Lineær, Probing::RobinHood] {
+            fjern_én_og_én(probing, &rekkefølge);
+            rekkefølge.reverse();
+            fjern_én_og_én(probing, &rekkefølge);
+
+            // Annenhver fra hver ende, så hullene kommer midt i klyngene
+            let (første, andre) = rekkefølge.split_at(45);
+            let blandet: Vec<usize> = første
+                .iter()
+                .zip(andre.iter().rev())
+                .flat_map(|(&a, &b)| [a, b])
+                .collect();
+            fjern_én_og_én(probing, &blandet);
+        }
+    }
+
+    #[test]
+    fn fjern_fra_gammel_tabell_under_omhashing() {
+        for probing in Probing::ALLE {
+            let mut tabell = HashTabell2::new(103)
+                .med_probing(probing)
+                .med_inkrementell_omhashing(true);
+            let nøkler = nøkler(93);
+            for &nøkkel in &nøkler {
+                tabell.sett_inn(nøkkel);
+            }
+            assert!(!tabell.gammel.is_empty());
+
+            // Fjern alle nøklene som ennå står i den gamle tabellen
+            let i_gammel: Vec<usize> = nøkler
+                .iter()
+                .copied()
+                .filter(|&nøkkel| HashTabell2::finn_i(&tabell.gammel, probing, nøkkel).is_some())
+                .collect();
+            assert!(!i_gammel.is_empty());
+
+            for &nøkkel in &i_gammel {
+                let (plass, _) = HashTabell2::finn_i(&tabell.gammel, probing, nøkkel).unwrap();
+                assert!(tabell.fjern(nøkkel));
+                assert_eq!(tabell.gammel[plass], Plass::Slettet);
+                assert!(!tabell.inneholder(nøkkel));
+            }
+
+            let igjen = nøkler.len() - i_gammel.len();
+            assert_eq!(tabell.antall_verdier, igjen);
+
+            // Gravsteinene blir ikke flyttet over når resten av den gamle tabellen flyttes
+            for nøkkel in 10_000..10_020 {
+                tabell.sett_inn(nøkkel);
+            }
+            assert!(tabell.gammel.is_empty());
+            assert_eq!(tabell.antall_verdier, igjen + 20);
+            for &nøkkel in &nøkler {
+                assert_eq!(tabell.inneholder(nøkkel), !i_gammel.contains(&nøkkel));
+            }
+        }
+    }
+}
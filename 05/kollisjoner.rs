@@ -0,0 +1,149 @@
+use std::fmt::Display;
+
+/// En ny nøkkel som havnet på en plass i tabellen der det allerede sto nøkler
+pub struct Kollisjon<'a, K> {
+    pub nøkkel: &'a K,
+    /// Plassen i tabellen
+    pub plass: usize,
+    /// Nøklene som allerede sto på plassen, nyeste først
+    pub andre: Vec<&'a K>,
+}
+
+/// Får beskjed om hver kollisjon ved innsetting med `HashTabell::sett_inn`. Lukninger som tar en
+/// `&Kollisjon` er også observatører, så en kollisjon kan formateres akkurat slik den som kaller vil
+pub trait Kollisjonsobservatør<K> {
+    fn kollisjon(&mut self, kollisjon: &Kollisjon<K>);
+}
+
+impl<K, F: FnMut(&Kollisjon<K>)> Kollisjonsobservatør<K> for F {
+    fn kollisjon(&mut self, kollisjon: &Kollisjon<K>) {
+        self(kollisjon)
+    }
+}
+
+/// Skriver ut hver kollisjon som "ny kolliderte med a, b,"
+pub struct Utskrift;
+
+impl<K: Display> Kollisjonsobservatør<K> for Utskrift {
+    fn kollisjon(&mut self, kollisjon: &Kollisjon<K>) {
+        print!("{} kolliderte med", kollisjon.nøkkel);
+        for annen in &kollisjon.andre {
+            print!(" {annen},");
+        }
+        println!();
+    }
+}
+
+/// Teller kollisjonene den får beskjed om, uavhengig av hva tabellen har telt fra før,
+/// og skriver ingenting. Brukes også for å skjule kollisjonene
+#[derive(Default)]
+pub struct Teller {
+    pub antall: usize,
+}
+
+impl<K> Kollisjonsobservatør<K> for Teller {
+    fn kollisjon(&mut self, _kollisjon: &Kollisjon<K>) {
+        self.antall += 1;
+    }
+}
+
+/// Tar vare på hver kollisjon: den nye nøkkelen, plassen og nøklene som sto der fra før
+pub struct Logg<K> {
+    pub kollisjoner: Vec<(K, usize, Vec<K>)>,
+}
+
+impl<K> Default for Logg<K> {
+    fn default() -> Self {
+        Self {
+            kollisjoner: Vec::new(),
+        }
+    }
+}
+
+impl<K: Clone> Kollisjonsobservatør<K> for Logg<K> {
+    fn kollisjon(&mut self, kollisjon: &Kollisjon<K>) {
+        self.kollisjoner.push((
+            kollisjon.nøkkel.clone(),
+            kollisjon.plass,
+            kollisjon.andre.iter().map(|&annen| annen.clone()).collect(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HashTabell;
+
+    /// En tabell med én plass som aldri utvides, så alle nøklene kolliderer
+    fn én_plass() -> HashTabell<String, usize> {
+        HashTabell::new(1).med_maks_lasttall(f32::INFINITY)
+    }
+
+    #[test]
+    fn teller_teller_kollisjonene() {
+        let mut tabell = HashTabell::new(7);
+        let mut teller = Teller::default();
+
+        for nummer in 0..50 {
+            tabell.sett_inn(format!("navn {nummer}"), nummer, &mut teller);
+        }
+
+        assert!(teller.antall > 0);
+        assert_eq!(teller.antall, tabell.kollisjoner);
+    }
+
+    #[test]
+    fn teller_begynner_på_null() {
+        let mut tabell = én_plass();
+        tabell.sett_inn("a".to_string(), 1, &mut Teller::default());
+        tabell.sett_inn("b".to_string(), 2, &mut Teller::default());
+
+        let mut teller = Teller::default();
+        tabell.sett_inn("c".to_string(), 3, &mut teller);
+        // En nøkkel som finnes fra før er ingen kollisjon
+        tabell.sett_inn("a".to_string(), 4, &mut teller);
+
+        assert_eq!(teller.antall, 1);
+        assert_eq!(tabell.kollisjoner, 2);
+    }
+
+    #[test]
+    fn logg_tar_vare_på_kollisjonene() {
+        let mut tabell = én_plass();
+        let mut logg = Logg::default();
+
+        for (linjenummer, navn) in ["a", "b", "c", "b"].iter().enumerate() {
+            tabell.sett_inn(navn.to_string(), linjenummer, &mut logg);
+        }
+
+        let streng = |navn: &[&str]| navn.iter().map(|navn| navn.to_string()).collect();
+        assert_eq!(
+            logg.kollisjoner,
+            [
+                ("b".to_string(), 0, streng(&["a"])),
+                ("c".to_string(), 0, streng(&["b", "a"])),
+            ]
+        );
+    }
+
+    #[test]
+    fn lukning_formaterer_kollisjonene() {
+        let mut tabell = én_plass();
+        let mut linjer = Vec::new();
+        let mut formater = |kollisjon: &Kollisjon<String>| {
+            linjer.push(format!(
+                "{}@{}:{}",
+                kollisjon.nøkkel,
+                kollisjon.plass,
+                kollisjon.andre.len()
+            ))
+        };
+
+        for navn in ["a", "b", "c"] {
+            tabell.sett_inn(navn.to_string(), 0, &mut formater);
+        }
+
+        assert_eq!(linjer, ["b@0:1", "c@0:2"]);
+    }
+}
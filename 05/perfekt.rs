@@ -0,0 +1,481 @@
+use std::collections::HashSet;
+use std::fs;
+use std::hash::Hasher;
+
+use crate::hashfunksjoner::{bland, Hashfunksjon, SipHash};
+
+/// Gjennomsnittlig antall nøkler per bøtte. Større bøtter gir færre forskyvninger å lagre,
+/// men gjør det vanskeligere å finne en forskyvning som passer for hele bøtta
+const NØKLER_PER_BØTTE: usize = 5;
+
+/// Starten på en fil med en lagret perfekt hashfunksjon
+const FILMERKE: &[u8; 8] = b"MPHF0001";
+
+/// Minimal perfekt hashfunksjon for en fast mengde nøkler, laget med CHD (compress, hash and displace).
+/// Hver av de n nøklene får sin egen plass i 0..n, uten kollisjoner og uten tomme plasser.
+///
+/// Nøklene fordeles først i bøtter med en vanlig hashfunksjon. Hver bøtte får så en forskyvning (d0, d1),
+/// og en nøkkel i bøtta havner på plass (f1 + d0 * f2 + d1) mod n, der f1 og f2 er to hasher av nøkkelen.
+/// Forskyvningene velges én bøtte av gangen, største bøtte først, så ingen nøkler havner på samme plass.
+/// Bare forskyvningene lagres, så funksjonen tar noen få bit per nøkkel uansett hvor lange nøklene er
+pub struct PerfektHash {
+    /// Antall nøkler, og dermed antall plasser
+    antall: usize,
+    bøtter: usize,
+    /// Nøkkelen til SipHash, som velges på nytt om ingen forskyvninger passer
+    frø: u64,
+    /// Antall bit hver forskyvning er lagret med
+    bredde: u32,
+    /// Forskyvningen til hver bøtte, pakket tett med `bredde` bit hver. Forskyvning nummer i er (i / n, i mod n)
+    forskyvninger: Vec<u64>,
+}
+
+/// Tettpakker verdiene med angitt antall bit hver, i 64-bits ord
+fn pakk(verdier: &[u64], bredde: u32) -> Vec<u64> {
+    let mut ord = vec![0_u64; (verdier.len() * bredde as usize).div_ceil(64)];
+
+    for (indeks, &verdi) in verdier.iter().enumerate() {
+        let bit = indeks * bredde as usize;
+        ord[bit / 64] |= verdi << (bit % 64);
+
+        // Verdien kan gå over grensen til neste ord
+        if bit % 64 + bredde as usize > 64 {
+            ord[bit / 64 + 1] |= verdi >> (64 - bit % 64);
+        }
+    }
+
+    ord
+}
+
+/// Henter verdi nummer `indeks` fra ord pakket med `pakk`
+fn hent(ord: &[u64], bredde: u32, indeks: usize) -> u64 {
+    let bit = indeks * bredde as usize;
+    let maske = if bredde == 64 {
+        u64::MAX
+    } else {
+        (1 << bredde) - 1
+    };
+
+    let mut verdi = ord[bit / 64] >> (bit % 64);
+    if bit % 64 + bredde as usize > 64 {
+        verdi |= ord[bit / 64 + 1] << (64 - bit % 64);
+    }
+
+    verdi & maske
+}
+
+/// Hasher bytene i en nøkkel med SipHash og angitt frø
+fn grunnhash(nøkkel: &str, frø: u64) -> u64 {
+    let mut hasher = SipHash { k0: frø, k1: 0 }.hasher(0);
+    hasher.write(nøkkel.as_bytes());
+
+    hasher.finish()
+}
+
+impl PerfektHash {
+    /// Lager en minimal perfekt hashfunksjon for nøklene, som ikke kan inneholde samme nøkkel flere ganger
+    pub fn bygg<S: AsRef<str>>(nøkler: &[S]) -> Result<Self, String> {
+        if nøkler.is_empty() {
+            return Err("Kan ikke lage en perfekt hashfunksjon uten nøkler".to_string());
+        }
+
+        let mut sett = HashSet::new();
+        for nøkkel in nøkler {
+            if !sett.insert(nøkkel.as_ref()) {
+                return Err(format!(
+                    "\"{}\" finnes flere ganger blant nøklene",
+                    nøkkel.as_ref()
+                ));
+            }
+        }
+
+        // Med så få forsøk per frø som det er forskyvninger å velge mellom er det svært lite sannsynlig
+        // å trenge mer enn noen få frø
+        (0..)
+            .find_map(|frø| Self::prøv_frø(nøkler, frø))
+            .ok_or_else(|| "Fant ingen perfekt hashfunksjon".to_string())
+    }
+
+    /// Prøver å finne forskyvninger for alle bøttene med angitt frø
+    fn prøv_frø<S: AsRef<str>>(nøkler: &[S], frø: u64) -> Option<Self> {
+        let antall = nøkler.len();
+        let bøtter = antall.div_ceil(NØKLER_PER_BØTTE);
+
+        let mut funksjon = Self {
+            antall,
+            bøtter,
+            frø,
+            bredde: 0,
+            forskyvninger: Vec::new(),
+        };
+
+        // Fordel hashene til nøklene i bøtter, og ta de største bøttene først mens det er mest ledig plass
+        let mut innhold: Vec<Vec<u64>> = vec![Vec::new(); bøtter];
+        for nøkkel in nøkler {
+            let hash = grunnhash(nøkkel.as_ref(), frø);
+            innhold[(hash % bøtter as u64) as usize].push(hash);
+        }
+
+        let mut rekkefølge: Vec<usize> = (0..bøtter).collect();
+        rekkefølge.sort_by_key(|&bøtte| std::cmp::Reverse(innhold[bøtte].len()));
+
+        let mut opptatt = vec![false; antall];
+        let mut forskyvninger = vec![0_u64; bøtter];
+        let mut plasser = Vec::with_capacity(NØKLER_PER_BØTTE);
+
+        for bøtte in rekkefølge {
+            if innhold[bøtte].is_empty() {
+                break;
+            }
+
+            // Prøv forskyvning 0, 1, 2, ... til alle nøklene i bøtta får hver sin ledige plass
+            let passer = (0..(antall * antall) as u64).find(|&forskyvning| {
+                plasser.clear();
+                for &hash in &innhold[bøtte] {
+                    let plass = funksjon.plass(hash, forskyvning);
+                    if opptatt[plass] || plasser.contains(&plass) {
+                        return false;
+                    }
+                    plasser.push(plass);
+                }
+                true
+            })?;
+
+            for &plass in &plasser {
+                opptatt[plass] = true;
+            }
+            forskyvninger[bøtte] = passer;
+        }
+
+        let størst = forskyvninger.iter().copied().max().unwrap_or(0);
+        funksjon.bredde = u32::max(64 - størst.leading_zeros(), 1);
+        funksjon.forskyvninger = pakk(&forskyvninger, funksjon.bredde);
+
+        Some(funksjon)
+    }
+
+    /// Plassen en nøkkel med angitt hash får med angitt forskyvning
+    fn plass(&self, hash: u64, forskyvning: u64) -> usize {
+        let n = self.antall as u64;
+        let (d0, d1) = (forskyvning / n, forskyvning % n);
+        let f1 = bland(hash) % n;
+        let f2 = bland(bland(hash)) % n;
+
+        ((f1 + d0 * f2 % n + d1) % n) as usize
+    }
+
+    /// Plassen til nøkkelen, i 0..n. Hver nøkkel funksjonen ble bygget for får sin egen plass,
+    /// mens andre nøkler får en vilkårlig plass
+    pub fn indeks(&self, nøkkel: &str) -> usize {
+        let hash = grunnhash(nøkkel, self.frø);
+        let bøtte = (hash % self.bøtter as u64) as usize;
+
+        self.plass(hash, hent(&self.forskyvninger, self.bredde, bøtte))
+    }
+
+    pub fn len(&self) -> usize {
+        self.antall
+    }
+
+    /// Antall bit forskyvningene tar per nøkkel. Hodet med antall, frø osv. har fast størrelse og er ikke med
+    pub fn bit_per_nøkkel(&self) -> f64 {
+        (self.forskyvninger.len() * 64) as f64 / self.antall as f64
+    }
+
+    /// Funksjonen som bytes: filmerket, så antall, bøtter, frø og bredde, så forskyvningene, alle som u64 i little endian
+    fn til_bytes(&self) -> Vec<u8> {
+        let mut bytes = FILMERKE.to_vec();
+
+        let hode = [
+            self.antall as u64,
+            self.bøtter as u64,
+            self.frø,
+            self.bredde as u64,
+        ];
+        for ord in hode.iter().chain(&self.forskyvninger) {
+            bytes.extend_from_slice(&ord.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    pub fn lagre(&self, filnavn: &str) -> Result<(), String> {
+        fs::write(filnavn, self.til_bytes())
+            .map_err(|feil| format!("Kunne ikke skrive til \"{filnavn}\": {feil}"))
+    }
+
+    pub fn les(filnavn: &str) -> Result<Self, String> {
+        let bytes =
+            fs::read(filnavn).map_err(|feil| format!("Kunne ikke lese \"{filnavn}\": {feil}"))?;
+        let ugyldig = || format!("\"{filnavn}\" er ikke en lagret perfekt hashfunksjon");
+
+        let resten = bytes.strip_prefix(FILMERKE).ok_or_else(ugyldig)?;
+        if resten.len() % 8 != 0 || resten.len() < 4 * 8 {
+            return Err(ugyldig());
+        }
+
+        let ord: Vec<u64> = resten
+            .chunks_exact(8)
+            .map(|ord| u64::from_le_bytes(ord.try_into().unwrap()))
+            .collect();
+
+        let funksjon = Self {
+            antall: ord[0] as usize,
+            bøtter: ord[1] as usize,
+            frø: ord[2],
+            bredde: ord[3] as u32,
+            forskyvninger: ord[4..].to_vec(),
+        };
+
+        // Et ødelagt antall bøtter kan gi overflyt når antall bit regnes ut, og da er filen ugyldig
+        let gyldig = funksjon.antall > 0
+            && funksjon.bøtter > 0
+            && (1..=64).contains(&funksjon.bredde)
+            && funksjon
+                .bøtter
+                .checked_mul(funksjon.bredde as usize)
+                .is_some_and(|bit| funksjon.forskyvninger.len() == bit.div_ceil(64));
+
+        if gyldig {
+            Ok(funksjon)
+        } else {
+            Err(ugyldig())
+        }
+    }
+}
+
+/// En fast mengde nøkler ordnet etter den perfekte hashfunksjonen, så et oppslag er én hash og én sammenligning
+pub struct PerfektTabell {
+    funksjon: PerfektHash,
+    nøkler: Vec<String>,
+}
+
+impl PerfektTabell {
+    /// Ordner nøklene etter funksjonen, som må være bygget for akkurat disse nøklene
+    pub fn new(funksjon: PerfektHash, nøkler: Vec<String>) -> Result<Self, String> {
+        if nøkler.len() != funksjon.len() {
+            return Err(format!(
+                "Funksjonen er bygget for {} nøkler, men fikk {}",
+                funksjon.len(),
+                nøkler.len()
+            ));
+        }
+
+        let mut ordnet = vec![String::new(); nøkler.len()];
+        let mut brukt = vec![false; nøkler.len()];
+
+        for nøkkel in nøkler {
+            let indeks = funksjon.indeks(&nøkkel);
+            if brukt[indeks] {
+                return Err(format!(
+                    "\"{nøkkel}\" og \"{}\" fikk samme plass, så funksjonen er ikke bygget for disse nøklene",
+                    ordnet[indeks]
+                ));
+            }
+            brukt[indeks] = true;
+            ordnet[indeks] = nøkkel;
+        }
+
+        Ok(Self {
+            funksjon,
+            nøkler: ordnet,
+        })
+    }
+
+    /// Plassen til nøkkelen, om den er en av nøklene
+    pub fn plass(&self, nøkkel: &str) -> Option<usize> {
+        let indeks = self.funksjon.indeks(nøkkel);
+        (self.nøkler[indeks] == nøkkel).then_some(indeks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    fn nøkler(antall: usize) -> Vec<String> {
+        (0..antall).map(|i| format!("nøkkel {i}")).collect()
+    }
+
+    fn midlertidig_fil(navn: &str) -> String {
+        env::temp_dir()
+            .join(format!("perfekt-{navn}-{}", process::id()))
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    /// Sjekker at hver nøkkel får sin egen plass i 0..n
+    fn sjekk_perfekt(funksjon: &PerfektHash, nøkler: &[String]) {
+        let mut brukt = vec![false; nøkler.len()];
+        for nøkkel in nøkler {
+            let indeks = funksjon.indeks(nøkkel);
+            assert!(indeks < nøkler.len());
+            assert!(!brukt[indeks], "to nøkler fikk plass {indeks}");
+            brukt[indeks] = true;
+        }
+    }
+
+    #[test]
+    fn hver_nøkkel_får_sin_egen_plass() {
+        for antall in [1, 2, 3, 5, 6, 100, 5000] {
+            let nøkler = nøkler(antall);
+            let funksjon = PerfektHash::bygg(&nøkler).unwrap();
+
+            assert_eq!(funksjon.len(), antall);
+            sjekk_perfekt(&funksjon, &nøkler);
+        }
+    }
+
+    #[test]
+    fn avviser_duplikater_og_tom_mengde() {
+        let tom: [&str; 0] = [];
+        assert_eq!(
+            PerfektHash::bygg(&tom).err().unwrap(),
+            "Kan ikke lage en perfekt hashfunksjon uten nøkler"
+        );
+        assert_eq!(
+            PerfektHash::bygg(&["a", "b", "a"]).err().unwrap(),
+            "\"a\" finnes flere ganger blant nøklene"
+        );
+    }
+
+    #[test]
+    fn pakk_og_hent() {
+        let mut tilstand = 1_u64;
+        for bredde in 1..=64 {
+            let maske = if bredde == 64 {
+                u64::MAX
+            } else {
+                (1 << bredde) - 1
+            };
+
+            // Med 130 verdier krysser noen av dem grensen mellom to ord for alle bredder som ikke deler 64
+            let mut verdier: Vec<u64> = (0..130)
+                .map(|_| {
+                    tilstand = bland(tilstand);
+                    tilstand & maske
+                })
+                .collect();
+            verdier[0] = maske;
+            verdier[1] = 0;
+
+            let ord = pakk(&verdier, bredde);
+            assert_eq!(ord.len(), (130 * bredde as usize).div_ceil(64));
+
+            for (indeks, &verdi) in verdier.iter().enumerate() {
+                assert_eq!(
+                    hent(&ord, bredde, indeks),
+                    verdi,
+                    "bredde {bredde}, indeks {indeks}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn verdi_over_ordgrensen() {
+        // Med bredde 24 ligger verdi nummer 2 på bit 48..72, altså delt mellom ord 0 og ord 1
+        let ord = pakk(&[0, 0, 0xabcdef, 0x123456], 24);
+        assert_eq!(ord, [0xcdef << 48, 0x1234_56ab]);
+        assert_eq!(hent(&ord, 24, 2), 0xabcdef);
+        assert_eq!(hent(&ord, 24, 3), 0x123456);
+    }
+
+    #[test]
+    fn lagre_og_les() {
+        let nøkler = nøkler(1000);
+        let funksjon = PerfektHash::bygg(&nøkler).unwrap();
+
+        let fil = midlertidig_fil("lagre");
+        funksjon.lagre(&fil).unwrap();
+        let lest = PerfektHash::les(&fil).unwrap();
+        fs::remove_file(&fil).unwrap();
+
+        assert_eq!(lest.til_bytes(), funksjon.til_bytes());
+        for nøkkel in &nøkler {
+            assert_eq!(lest.indeks(nøkkel), funksjon.indeks(nøkkel));
+        }
+    }
+
+    #[test]
+    fn avviser_ødelagt_fil() {
+        let bytes = PerfektHash::bygg(&nøkler(100)).unwrap().til_bytes();
+        let ord = |bytes: &mut Vec<u8>, nummer: usize, verdi: u64| {
+            let start = 8 + 8 * nummer;
+            bytes[start..start + 8].copy_from_slice(&verdi.to_le_bytes());
+        };
+
+        let mut feil_merke = bytes.clone();
+        feil_merke[0] = b'X';
+
+        let mut null_nøkler = bytes.clone();
+        ord(&mut null_nøkler, 0, 0);
+
+        let mut null_bredde = bytes.clone();
+        ord(&mut null_bredde, 3, 0);
+
+        // Antall bøtter ganger bredden går over usize::MAX
+        let mut overflyt = bytes.clone();
+        ord(&mut overflyt, 1, u64::MAX / 2);
+        ord(&mut overflyt, 3, 64);
+
+        let mut feil_bøtter = bytes.clone();
+        ord(&mut feil_bøtter, 1, 1000);
+
+        let ødelagte = [
+            bytes[..bytes.len() - 8].to_vec(),
+            bytes[..bytes.len() - 3].to_vec(),
+            bytes[..20].to_vec(),
+            Vec::new(),
+            [bytes.as_slice(), &[0; 8]].concat(),
+            feil_merke,
+            null_nøkler,
+            null_bredde,
+            overflyt,
+            feil_bøtter,
+        ];
+
+        let fil = midlertidig_fil("ødelagt");
+        for (nummer, innhold) in ødelagte.iter().enumerate() {
+            fs::write(&fil, innhold).unwrap();
+            assert_eq!(
+                PerfektHash::les(&fil).err(),
+                Some(format!("\"{fil}\" er ikke en lagret perfekt hashfunksjon")),
+                "fil nummer {nummer}"
+            );
+        }
+        fs::remove_file(&fil).unwrap();
+
+        assert!(PerfektHash::les(&fil)
+            .err()
+            .unwrap()
+            .starts_with("Kunne ikke lese"));
+    }
+
+    #[test]
+    fn tabell_finner_bare_nøklene() {
+        let nøkler = nøkler(500);
+        let funksjon = PerfektHash::bygg(&nøkler).unwrap();
+        let tabell = PerfektTabell::new(funksjon, nøkler.clone()).unwrap();
+
+        for nøkkel in &nøkler {
+            assert_eq!(tabell.plass(nøkkel), Some(tabell.funksjon.indeks(nøkkel)));
+        }
+        for i in 500..1500 {
+            assert_eq!(tabell.plass(&format!("nøkkel {i}")), None);
+        }
+        assert_eq!(tabell.plass(""), None);
+    }
+
+    #[test]
+    fn tabell_avviser_andre_nøkler() {
+        let funksjon = PerfektHash::bygg(&nøkler(10)).unwrap();
+        assert_eq!(
+            PerfektTabell::new(funksjon, nøkler(9)).err().unwrap(),
+            "Funksjonen er bygget for 10 nøkler, men fikk 9"
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+
+/// Hvordan åpen adressering leter seg frem til en ledig plass når plassen en nøkkel hasher til er opptatt
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Probing {
+    /// Prøver neste plass, så den etter der osv. Raskt å gå gjennom, men kollisjoner klumper seg sammen
+    Lineær,
+    /// Prøver h + 1, h - 1, h + 4, h - 4, h + 9, ... Sprer klumpene, og kommer innom alle plassene
+    /// når kapasiteten er et primtall på formen 4k + 3
+    Kvadratisk,
+    /// Lineær probing, der en nøkkel som har kommet lenger fra plassen sin enn den som står i veien
+    /// tar over plassen, og den andre leter videre. Jevner ut hvor langt nøklene havner fra plassen sin
+    RobinHood,
+    /// Hopper med en hopplengde som avhenger av nøkkelen, så to nøkler med samme startplass
+    /// sjelden følger samme vei videre
+    Dobbel,
+}
+
+impl Probing {
+    pub const ALLE: [Probing; 4] = [
+        Self::Dobbel,
+        Self::Lineær,
+        Self::Kvadratisk,
+        Self::RobinHood,
+    ];
+
+    /// Tolker navnet på en probing slik det skrives på kommandolinjen
+    pub fn fra_navn(navn: &str) -> Option<Self> {
+        match navn {
+            "dobbel" => Some(Self::Dobbel),
+            "lineær" | "linear" => Some(Self::Lineær),
+            "kvadratisk" => Some(Self::Kvadratisk),
+            "robin-hood" => Some(Self::RobinHood),
+            _ => None,
+        }
+    }
+
+    pub fn navn(self) -> &'static str {
+        match self {
+            Self::Dobbel => "dobbel",
+            Self::Lineær => "lineær",
+            Self::Kvadratisk => "kvadratisk",
+            Self::RobinHood => "robin-hood",
+        }
+    }
+}
+
+/// Plassene en nøkkel prøver, i rekkefølge, i en tabell med angitt kapasitet. Første plass er alltid
+/// startplassen, og sekvensen tar aldri slutt, så den som bruker den må selv stoppe
+pub struct Probesekvens {
+    probing: Probing,
+    start: usize,
+    plass: usize,
+    /// Hopplengden ved dobbel hashing
+    hopp: usize,
+    forsøk: usize,
+    kapasitet: usize,
+}
+
+impl Probesekvens {
+    pub fn new(probing: Probing, start: usize, hopp: usize, kapasitet: usize) -> Self {
+        Self {
+            probing,
+            start,
+            plass: start,
+            hopp,
+            forsøk: 0,
+            kapasitet,
+        }
+    }
+}
+
+impl Iterator for Probesekvens {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let plass = self.plass;
+        self.forsøk += 1;
+
+        self.plass = match self.probing {
+            Probing::Lineær | Probing::RobinHood => (plass + 1) % self.kapasitet,
+            Probing::Dobbel => (plass + self.hopp) % self.kapasitet,
+            Probing::Kvadratisk => {
+                // Forsøk 1 og 2 er ±1², forsøk 3 og 4 er ±2² osv.
+                let j = self.forsøk.div_ceil(2) as u128;
+                let forskyvning = (j * j % self.kapasitet as u128) as usize;
+
+                if !self.forsøk.is_multiple_of(2) {
+                    (self.start + forskyvning) % self.kapasitet
+                } else {
+                    (self.start + self.kapasitet - forskyvning) % self.kapasitet
+                }
+            }
+        };
+
+        Some(plass)
+    }
+}
+
+/// Hvor mange plasser oppslag må innom før de finner nøklene sine
+#[derive(Default)]
+pub struct Probestatistikk {
+    /// antall[l] er antall nøkler som ble funnet på plass nummer l i probesekvensen sin
+    antall: Vec<usize>,
+}
+
+impl Probestatistikk {
+    /// Lengder over dette samles i én rad i histogrammet
+    const MAKS_RADER: usize = 20;
+
+    /// Bredden på den lengste søylen i histogrammet
+    const SØYLEBREDDE: usize = 50;
+
+    pub fn legg_til(&mut self, probelengde: usize) {
+        if self.antall.len() <= probelengde {
+            self.antall.resize(probelengde + 1, 0);
+        }
+        self.antall[probelengde] += 1;
+    }
+
+    /// Gjennomsnittlig probelengde, eller 0 om ingen nøkler er telt
+    pub fn gjennomsnitt(&self) -> f64 {
+        let nøkler: usize = self.antall.iter().sum();
+        if nøkler == 0 {
+            return 0.0;
+        }
+
+        let sum: usize = self
+            .antall
+            .iter()
+            .enumerate()
+            .map(|(lengde, antall)| lengde * antall)
+            .sum();
+
+        sum as f64 / nøkler as f64
+    }
+
+    pub fn lengste(&self) -> usize {
+        self.antall.len().saturating_sub(1)
+    }
+}
+
+// Skriver et histogram med én rad per probelengde, og søyler i forhold til den største raden
+impl fmt::Display for Probestatistikk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut rader: Vec<(String, usize)> = (1..self.antall.len().min(Self::MAKS_RADER + 1))
+            .map(|lengde| (lengde.to_string(), self.antall[lengde]))
+            .collect();
+
+        if self.antall.len() > Self::MAKS_RADER + 1 {
+            let resten = self.antall[Self::MAKS_RADER + 1..].iter().sum();
+            rader.push((format!("{}+", Self::MAKS_RADER + 1), resten));
+        }
+
+        let størst = rader.iter().map(|&(_, antall)| antall).max().unwrap_or(0);
+
+        for (lengde, antall) in rader {
+            let søyle = "#".repeat(antall * Self::SØYLEBREDDE / størst.max(1));
+            writeln!(
+                f,
+                "{lengde:>4} | {søyle:<bredde$} {antall}",
+                bredde = Self::SØYLEBREDDE
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// De første `antall` plassene i probesekvensen
+    fn plasser(
+        probing: Probing,
+        start: usize,
+        hopp: usize,
+        kapasitet: usize,
+        antall: usize,
+    ) -> Vec<usize> {
+        Probesekvens::new(probing, start, hopp, kapasitet)
+            .take(antall)
+            .collect()
+    }
+
+    /// Om de første `kapasitet` plassene i sekvensen er alle plassene i tabellen
+    fn innom_alle(probing: Probing, start: usize, hopp: usize, kapasitet: usize) -> bool {
+        let mut sett = plasser(probing, start, hopp, kapasitet, kapasitet);
+        sett.sort();
+        sett == (0..kapasitet).collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn lineær_og_robin_hood_går_rundt() {
+        assert_eq!(plasser(Probing::Lineær, 5, 3, 7, 4), [5, 6, 0, 1]);
+        assert_eq!(plasser(Probing::RobinHood, 5, 3, 7, 4), [5, 6, 0, 1]);
+    }
+
+    #[test]
+    fn kvadratisk_veksler_mellom_pluss_og_minus() {
+        assert_eq!(plasser(Probing::Kvadratisk, 5, 1, 19, 5), [5, 6, 4, 9, 1]);
+    }
+
+    #[test]
+    fn dobbel_hopper_med_hopplengden() {
+        assert_eq!(plasser(Probing::Dobbel, 5, 3, 7, 4), [5, 1, 4, 0]);
+    }
+
+    #[test]
+    fn alle_probingene_kommer_innom_alle_plassene() {
+        // Primtall på formen 4k + 3, slik HashTabell2 bruker
+        for kapasitet in [7, 11, 19, 23, 103] {
+            for start in 0..kapasitet {
+                for probing in Probing::ALLE {
+                    assert!(
+                        innom_alle(probing, start, 1 + start % (kapasitet - 1), kapasitet),
+                        "{} med kapasitet {kapasitet} fra {start}",
+                        probing.navn()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn navn_kan_leses_inn_igjen() {
+        for probing in Probing::ALLE {
+            assert_eq!(Probing::fra_navn(probing.navn()), Some(probing));
+        }
+        assert_eq!(Probing::fra_navn("linear"), Some(Probing::Lineær));
+        assert_eq!(Probing::fra_navn("kubisk"), None);
+    }
+
+    #[test]
+    fn probestatistikk() {
+        let mut statistikk = Probestatistikk::default();
+        assert_eq!(statistikk.lengste(), 0);
+        assert_eq!(statistikk.gjennomsnitt(), 0.0);
+        assert_eq!(statistikk.to_string(), "");
+
+        for probelengde in [1, 1, 1, 3] {
+            statistikk.legg_til(probelengde);
+        }
+
+        assert_eq!(statistikk.lengste(), 3);
+        assert_eq!(statistikk.gjennomsnitt(), 1.5);
+        assert_eq!(statistikk.to_string().lines().count(), 3);
+    }
+}